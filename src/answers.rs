@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Default location of the answers file, relative to the crate root.
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// Known-correct answers keyed by day and part.
+///
+/// The file is a small TOML subset, one table per day:
+///
+/// ```toml
+/// [d1]
+/// part1 = 2264607
+/// part2 = "19457120"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Self::parse(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+
+        for (i, line) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let parsed = table
+                    .trim()
+                    .strip_prefix('d')
+                    .and_then(|d| d.parse::<u32>().ok())
+                    .ok_or_else(|| format!("line {}: expected a table like [d1], got [{}]", line_no, table))?;
+                day = Some(parsed);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `partN = answer`", line_no))?;
+            let day = day.ok_or_else(|| format!("line {}: answer outside of a [dN] table", line_no))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|p| p.parse::<u32>().ok())
+                .ok_or_else(|| format!("line {}: expected a key like part1, got {}", line_no, key.trim()))?;
+
+            let value = value.trim();
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted
+                    .strip_suffix('"')
+                    .ok_or_else(|| format!("line {}: unterminated string", line_no))?,
+                None => value,
+            };
+
+            answers.insert((day, part), value.to_string());
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+// Cuts `line` at the first `#` that isn't inside a quoted string.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# known answers
+[d1]
+part1 = 11
+part2 = \"31\"
+
+[d23]
+part2 = \"co,de,ka,ta\" # password
+
+[d24]
+part1 = \"a # b\"
+",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(23, 2), Some("co,de,ka,ta"));
+        assert_eq!(answers.get(23, 1), None);
+        assert_eq!(answers.get(24, 1), Some("a # b"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("part1 = 3").unwrap_err().contains("line 1"));
+        assert!(Answers::parse("[day1]").is_err());
+        assert!(Answers::parse("[d1]\nanswer = 3").unwrap_err().contains("line 2"));
+        assert!(Answers::parse("[d1]\npart1 = \"3").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::input::InputSource;
+use crate::{AocError, Solution};
+use std::fmt::Write as _;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// How long to keep re-running each solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Stop after this many runs, even if there is time left.
+    pub max_runs: u32,
+    /// Stop once this much time has been spent, after at least one run.
+    pub budget: Duration,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_runs: 100,
+            budget: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Median timings for one day and part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u32,
+    pub part: u32,
+    pub runs: u32,
+    /// Time spent in [`Solution::parse`] alone.
+    pub parse: Duration,
+    /// Time spent in [`Solution::solve`], which parses the input again.
+    pub total: Duration,
+    /// Why the solution could not be timed: no input, an error or a panic.
+    pub error: Option<String>,
+}
+
+impl Timing {
+    /// Time spent solving once the input is parsed.
+    pub fn solve(&self) -> Duration {
+        self.total.saturating_sub(self.parse)
+    }
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort_unstable();
+    samples.get(samples.len() / 2).copied().unwrap_or_default()
+}
+
+/// Times `solution` on `input`, parsing and solving separately.
+pub fn bench(solution: &dyn Solution, input: Option<&str>, options: &Options) -> Timing {
+    let mut timing = Timing {
+        day: solution.day(),
+        part: solution.part(),
+        runs: 0,
+        parse: Duration::ZERO,
+        total: Duration::ZERO,
+        error: None,
+    };
+    let Some(input) = input else {
+        timing.error = Some("no input".to_string());
+        return timing;
+    };
+
+    let mut parse_samples = Vec::new();
+    let mut total_samples = Vec::new();
+    let started = Instant::now();
+    while timing.runs < options.max_runs.max(1) && (timing.runs == 0 || started.elapsed() < options.budget) {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let start = Instant::now();
+            solution.parse(input)?;
+            let parsed = start.elapsed();
+
+            let start = Instant::now();
+            solution.solve(input)?;
+            Ok::<_, AocError>((parsed, start.elapsed()))
+        }));
+        match result {
+            Ok(Ok((parse, total))) => {
+                parse_samples.push(parse);
+                total_samples.push(total);
+                timing.runs += 1;
+            }
+            Ok(Err(err)) => {
+                timing.error = Some(err.to_string());
+                return timing;
+            }
+            Err(_) => {
+                timing.error = Some("solver panicked".to_string());
+                return timing;
+            }
+        }
+    }
+
+    timing.parse = median(parse_samples);
+    timing.total = median(total_samples);
+    timing
+}
+
+/// Times every solution in turn, loading each day's input once.
+pub fn bench_all<'a>(
+    solutions: impl IntoIterator<Item = &'a dyn Solution>,
+    source: &InputSource,
+    options: &Options,
+) -> Vec<Timing> {
+    let mut timings = Vec::new();
+    let mut current: Option<(u32, Option<String>)> = None;
+
+    for solution in solutions {
+        let day = solution.day();
+        if current.as_ref().map(|(d, _)| *d) != Some(day) {
+            current = Some((day, source.load(day).ok()));
+        }
+        let input = current.as_ref().and_then(|(_, input)| input.as_deref());
+        timings.push(bench(solution, input, options));
+    }
+    timings
+}
+
+/// Formats `duration` with a unit that keeps three or four significant digits.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{}ns", nanos)
+    } else if nanos < 1e6 {
+        format!("{:.2}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Serializes `timings` as a JSON array, one object per line so that saved
+/// runs diff cleanly. Durations are in nanoseconds.
+pub fn to_json(timings: &[Timing]) -> String {
+    let mut out = String::from("[\n");
+    for (i, timing) in timings.iter().enumerate() {
+        write!(out, "  {{\"day\": {}, \"part\": {}", timing.day, timing.part).unwrap();
+        match &timing.error {
+            Some(error) => write!(out, ", \"error\": {}", json_string(error)).unwrap(),
+            None => write!(
+                out,
+                ", \"runs\": {}, \"parse_ns\": {}, \"solve_ns\": {}, \"total_ns\": {}",
+                timing.runs,
+                timing.parse.as_nanos(),
+                timing.solve().as_nanos(),
+                timing.total.as_nanos()
+            )
+            .unwrap(),
+        }
+        out += if i + 1 < timings.len() { "},\n" } else { "}\n" };
+    }
+    out += "]\n";
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    struct Echo;
+
+    impl Solution for Echo {
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn part(&self) -> u32 {
+            2
+        }
+
+        fn solve(&self, input: &str) -> Result<Answer, AocError> {
+            match input {
+                "" => Err(AocError::NoSolution),
+                "panic" => panic!("boom"),
+                _ => Ok(input.into()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_bench() {
+        let options = Options {
+            max_runs: 3,
+            budget: Duration::from_secs(10),
+        };
+
+        let timing = bench(&Echo, Some("42"), &options);
+        assert_eq!((timing.day, timing.part, timing.runs), (1, 2, 3));
+        assert_eq!(timing.error, None);
+
+        assert_eq!(bench(&Echo, Some(""), &options).error.as_deref(), Some("no solution"));
+        assert_eq!(bench(&Echo, Some("panic"), &options).error.as_deref(), Some("solver panicked"));
+        assert_eq!(bench(&Echo, None, &options).error.as_deref(), Some("no input"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35µs");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.50ms");
+        assert_eq!(format_duration(Duration::from_millis(2_250)), "2.25s");
+    }
+
+    #[test]
+    fn test_to_json() {
+        let timings = [
+            Timing {
+                day: 7,
+                part: 1,
+                runs: 5,
+                parse: Duration::from_nanos(100),
+                total: Duration::from_nanos(350),
+                error: None,
+            },
+            Timing {
+                day: 7,
+                part: 2,
+                runs: 0,
+                parse: Duration::ZERO,
+                total: Duration::ZERO,
+                error: Some("line 1: invalid number \"x\"".to_string()),
+            },
+        ];
+        assert_eq!(
+            to_json(&timings),
+            r#"[
+  {"day": 7, "part": 1, "runs": 5, "parse_ns": 100, "solve_ns": 250, "total_ns": 350},
+  {"day": 7, "part": 2, "error": "line 1: invalid number \"x\""}
+]
+"#
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+use std::ops::{BitXor, Shl, Shr};
+use std::str::FromStr;
+
+/// An arbitrary-precision unsigned integer, with just the operations the
+/// puzzles need: parsing and printing in decimal, shifts and xor.
+///
+/// Limbs are little-endian and never end in a zero, so every value has
+/// exactly one representation and the derived equality is correct.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+/// Returned when parsing a [`BigUint`] from anything but decimal digits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError;
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid digit in unsigned integer")
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+impl BigUint {
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of bits needed to write the value, 0 for zero.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(&top) => 32 * self.limbs.len() as u64 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// The value if it fits in a `u64`.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [low] => Some(low as u64),
+            [low, high] => Some((high as u64) << 32 | low as u64),
+            _ => None,
+        }
+    }
+
+    /// The lowest 64 bits, discarding the rest.
+    pub fn low_u64(&self) -> u64 {
+        let low = self.limbs.first().copied().unwrap_or(0) as u64;
+        let high = self.limbs.get(1).copied().unwrap_or(0) as u64;
+        high << 32 | low
+    }
+
+    // self * factor + addend, in place.
+    fn mul_add(&mut self, factor: u32, addend: u32) {
+        let mut carry = addend as u64;
+        for limb in &mut self.limbs {
+            let value = *limb as u64 * factor as u64 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        *self = BigUint::from_limbs(std::mem::take(&mut self.limbs));
+    }
+
+    // Divides in place, returning the remainder.
+    fn div_rem(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.limbs.iter_mut().rev() {
+            let value = remainder << 32 | *limb as u64;
+            *limb = (value / divisor as u64) as u32;
+            remainder = value % divisor as u64;
+        }
+        *self = BigUint::from_limbs(std::mem::take(&mut self.limbs));
+        remainder as u32
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        BigUint::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(n: u128) -> Self {
+        BigUint::from_limbs((0..4).map(|i| (n >> (32 * i)) as u32).collect())
+    }
+}
+
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseBigUintError);
+        }
+        let mut n = BigUint::default();
+        for c in s.chars() {
+            let digit = c.to_digit(10).ok_or(ParseBigUintError)?;
+            n.mul_add(10, digit);
+        }
+        Ok(n)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+        // Peel off nine decimal digits at a time, least significant first
+        let mut n = self.clone();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_rem(1_000_000_000));
+        }
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits += &format!("{:09}", chunk);
+        }
+        f.pad(&digits)
+    }
+}
+
+impl Shr<u64> for &BigUint {
+    type Output = BigUint;
+
+    /// Shifting by at least [`BigUint::bits`] gives zero.
+    fn shr(self, amount: u64) -> BigUint {
+        if amount >= self.bits() {
+            return BigUint::default();
+        }
+        let (words, bits) = ((amount / 32) as usize, (amount % 32) as u32);
+        let limbs = &self.limbs[words..];
+        let shifted = (0..limbs.len())
+            .map(|i| {
+                let high = limbs.get(i + 1).copied().unwrap_or(0) as u64;
+                ((high << 32 | limbs[i] as u64) >> bits) as u32
+            })
+            .collect();
+        BigUint::from_limbs(shifted)
+    }
+}
+
+impl Shl<u64> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, amount: u64) -> BigUint {
+        if self.is_zero() {
+            return BigUint::default();
+        }
+        let (words, bits) = ((amount / 32) as usize, (amount % 32) as u32);
+        let mut limbs = vec![0; words];
+        let mut carry = 0u32;
+        for &limb in &self.limbs {
+            let value = (limb as u64) << bits | carry as u64;
+            limbs.push(value as u32);
+            carry = (value >> 32) as u32;
+        }
+        limbs.push(carry);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl BitXor for &BigUint {
+    type Output = BigUint;
+
+    fn bitxor(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let limb = |n: &BigUint, i: usize| n.limbs.get(i).copied().unwrap_or(0);
+        BigUint::from_limbs((0..len).map(|i| limb(self, i) ^ limb(other, i)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "340282366920938463463374607431768211456000000001";
+        let n: BigUint = text.parse().unwrap();
+        assert_eq!(n.to_string(), text);
+        assert_eq!("0".parse::<BigUint>().unwrap(), BigUint::default());
+        assert_eq!("007".parse::<BigUint>().unwrap(), BigUint::from(7u64));
+        assert_eq!(BigUint::from(1_000_000_000u64).to_string(), "1000000000");
+        assert_eq!("".parse::<BigUint>(), Err(ParseBigUintError));
+        assert_eq!("-1".parse::<BigUint>(), Err(ParseBigUintError));
+    }
+
+    #[test]
+    fn test_shifts() {
+        let one = BigUint::from(1u64);
+        let big = &one << 200;
+        assert_eq!(big.bits(), 201);
+        assert_eq!(big.to_u64(), None);
+        assert_eq!(&big >> 199, BigUint::from(2u64));
+        assert_eq!(&big >> 201, BigUint::default());
+        assert_eq!(&big >> u64::MAX, BigUint::default());
+
+        let n = BigUint::from(u128::MAX);
+        assert_eq!(&n >> 64, BigUint::from(u64::MAX));
+        assert_eq!((&n >> 3).low_u64(), u64::MAX);
+        assert_eq!((&n << 4).bits(), 132);
+    }
+
+    #[test]
+    fn test_xor() {
+        let a = BigUint::from(0b1100u64);
+        let b = BigUint::from(0b1010u64);
+        assert_eq!(&a ^ &b, BigUint::from(0b0110u64));
+        assert!((&a ^ &a).is_zero());
+
+        let big = &BigUint::from(5u64) << 100;
+        assert_eq!(&(&big ^ &a) ^ &big, a);
+    }
+}
@@ -0,0 +1,435 @@
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc2024::answers::{Answers, DEFAULT_ANSWERS_FILE};
+use aoc2024::bench::{self, format_duration};
+use aoc2024::geometry::Point;
+use aoc2024::input::InputSource;
+use aoc2024::verify::{self, Status};
+use aoc2024::{AocError, Solution};
+
+const USAGE: &str = "usage:
+    aoc2024 run <day> [<part>] [--input <path>|-]
+    aoc2024 run --all
+    aoc2024 run 6 --replay [--obstruction <x>,<y>] [--delay <ms>]
+    aoc2024 run 17 --disasm
+    aoc2024 run 17 --trace [--break <ip>]... [--budget <n>]
+    aoc2024 run 18 --race [--size <n>]
+    aoc2024 run 24 --dot
+    aoc2024 run 24 --check [--swap <wire>,<wire>]... [--cases <n>] [--seed <n>]
+    aoc2024 verify [--answers <path>]
+    aoc2024 bench [<day> [<part>]] [--runs <n>] [--json <path>]
+
+Inputs are read from $AOC_INPUT_DIR/dN.in (default: src/dN.in) unless
+--input names a file, or `-` for stdin. Known answers are read from
+answers.toml by default. `bench` times parsing and solving separately,
+taking the median of up to --runs runs (default 100, at most ~1s each).
+--replay animates the day 6 guard one step every --delay ms (default 50)
+with an extra --obstruction, or draws the whole route when not on a terminal.
+--disasm prints the day 17 program as pseudocode instead of solving it;
+--trace prints every step it takes, stopping at the first --break or after
+--budget steps (default 100000), and fails if the budget runs out. --race
+walks day 18's --size square (default 71) while byte i lands at step i.
+--dot prints the day 24 circuit as Graphviz DOT with the miswired gates in
+red. --check adds x and y on edge cases and --cases random operands
+(default 1000) after applying each --swap, and reports the smallest
+failing case.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Something other than the answers that `run` can print for one day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Replay,
+    Disasm,
+    Trace,
+    Race,
+    Dot,
+    Check,
+}
+
+impl View {
+    fn flag(self) -> &'static str {
+        match self {
+            View::Replay => "--replay",
+            View::Disasm => "--disasm",
+            View::Trace => "--trace",
+            View::Race => "--race",
+            View::Dot => "--dot",
+            View::Check => "--check",
+        }
+    }
+
+    fn day(self) -> u32 {
+        match self {
+            View::Replay => 6,
+            View::Disasm | View::Trace => 17,
+            View::Race => 18,
+            View::Dot | View::Check => 24,
+        }
+    }
+}
+
+/// Options accepted by `aoc2024 run`.
+struct RunArgs {
+    all: bool,
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<InputSource>,
+    view: Option<View>,
+    obstruction: Option<Point>,
+    delay: Option<u64>,
+    breakpoints: Vec<usize>,
+    budget: Option<u64>,
+    size: Option<usize>,
+    swaps: Vec<(String, String)>,
+    cases: Option<usize>,
+    seed: Option<u64>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut parsed = RunArgs {
+        all: false,
+        day: None,
+        part: None,
+        input: None,
+        view: None,
+        obstruction: None,
+        delay: None,
+        breakpoints: Vec::new(),
+        budget: None,
+        size: None,
+        swaps: Vec::new(),
+        cases: None,
+        seed: None,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => parsed.all = true,
+            "--replay" | "--disasm" | "--trace" | "--race" | "--dot" | "--check" => {
+                let view = match arg.as_str() {
+                    "--replay" => View::Replay,
+                    "--disasm" => View::Disasm,
+                    "--trace" => View::Trace,
+                    "--race" => View::Race,
+                    "--dot" => View::Dot,
+                    _ => View::Check,
+                };
+                if let Some(other) = parsed.view.replace(view) {
+                    return Err(format!("{} cannot be combined with {}", arg, other.flag()));
+                }
+            }
+            "--obstruction" => {
+                let cell = iter.next().ok_or("--obstruction needs a cell")?;
+                let (x, y) = cell.split_once(',').ok_or_else(|| format!("expected <x>,<y>, got {}", cell))?;
+                let coordinate = |c: &str| c.parse().map_err(|_| format!("invalid cell: {}", cell));
+                parsed.obstruction = Some(Point::new(coordinate(x)?, coordinate(y)?));
+            }
+            "--delay" => {
+                let delay = iter.next().ok_or("--delay needs milliseconds")?;
+                parsed.delay = Some(delay.parse().map_err(|_| format!("invalid delay: {}", delay))?);
+            }
+            "--break" => {
+                let ip = iter.next().ok_or("--break needs an instruction pointer")?;
+                parsed.breakpoints.push(ip.parse().map_err(|_| format!("invalid instruction pointer: {}", ip))?);
+            }
+            "--budget" => {
+                let budget = iter.next().ok_or("--budget needs a step count")?;
+                parsed.budget = Some(budget.parse().map_err(|_| format!("invalid step count: {}", budget))?);
+            }
+            "--size" => {
+                let size = iter.next().ok_or("--size needs a grid size")?;
+                let invalid = || format!("invalid grid size: {}", size);
+                parsed.size = Some(size.parse().ok().filter(|&size| size > 0).ok_or_else(invalid)?);
+            }
+            "--swap" => {
+                let pair = iter.next().ok_or("--swap needs two wires")?;
+                let (a, b) = pair.split_once(',').ok_or_else(|| format!("expected <wire>,<wire>, got {}", pair))?;
+                parsed.swaps.push((a.to_string(), b.to_string()));
+            }
+            "--cases" => {
+                let cases = iter.next().ok_or("--cases needs a count")?;
+                parsed.cases = Some(cases.parse().map_err(|_| format!("invalid case count: {}", cases))?);
+            }
+            "--seed" => {
+                let seed = iter.next().ok_or("--seed needs a number")?;
+                parsed.seed = Some(seed.parse().map_err(|_| format!("invalid seed: {}", seed))?);
+            }
+            "--input" => {
+                let path = iter.next().ok_or("--input needs a path")?;
+                parsed.input = Some(InputSource::from_arg(path));
+            }
+            _ if parsed.day.is_none() => {
+                parsed.day = Some(arg.parse().map_err(|_| format!("invalid day: {}", arg))?);
+            }
+            _ if parsed.part.is_none() => {
+                parsed.part = Some(arg.parse().map_err(|_| format!("invalid part: {}", arg))?);
+            }
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+
+    if parsed.all == parsed.day.is_some() {
+        return Err("expected either a day or --all".to_string());
+    }
+    if parsed.all && parsed.input.is_some() {
+        return Err("--input cannot be combined with --all".to_string());
+    }
+    if let Some(view) = parsed.view {
+        if parsed.day != Some(view.day()) {
+            return Err(format!("{} only works for day {}", view.flag(), view.day()));
+        }
+    }
+    if parsed.view != Some(View::Replay) && (parsed.obstruction.is_some() || parsed.delay.is_some()) {
+        return Err("--obstruction and --delay need --replay".to_string());
+    }
+    if parsed.view != Some(View::Trace) && (!parsed.breakpoints.is_empty() || parsed.budget.is_some()) {
+        return Err("--break and --budget need --trace".to_string());
+    }
+    if parsed.view != Some(View::Race) && parsed.size.is_some() {
+        return Err("--size needs --race".to_string());
+    }
+    if parsed.view != Some(View::Check) && (!parsed.swaps.is_empty() || parsed.cases.is_some() || parsed.seed.is_some()) {
+        return Err("--swap, --cases and --seed need --check".to_string());
+    }
+    Ok(parsed)
+}
+
+/// Runs the selected solutions, returning whether all of them succeeded.
+fn run(args: &[String]) -> Result<bool, String> {
+    let args = parse_run_args(args)?;
+    if let Some(view) = args.view {
+        let passed = |text: String| (text, true);
+        return show(view.day(), args.input, |input| match view {
+            // Animating only makes sense on a terminal, anywhere else the
+            // finished route is more useful
+            View::Replay if io::stdout().is_terminal() => {
+                let delay = Duration::from_millis(args.delay.unwrap_or(50));
+                aoc2024::tools::day6_replay(input, args.obstruction, delay, true, &mut io::stdout().lock())
+                    .map(|()| (String::new(), true))
+            }
+            View::Replay => aoc2024::tools::day6_route(input, args.obstruction).map(passed),
+            View::Disasm => aoc2024::tools::day17_disassembly(input).map(passed),
+            View::Trace => aoc2024::tools::day17_trace(input, &args.breakpoints, Some(args.budget.unwrap_or(100_000))),
+            View::Race => aoc2024::tools::day18_race(input, args.size.unwrap_or(71)).map(passed),
+            View::Dot => aoc2024::tools::day24_dot(input).map(passed),
+            View::Check => {
+                let cases = args.cases.unwrap_or(1000);
+                let counterexample = aoc2024::tools::day24_check(input, &args.swaps, cases, args.seed.unwrap_or(0))?;
+                Ok(match counterexample {
+                    Some(counterexample) => (format!("{}\n", counterexample), false),
+                    None => (format!("z = x + y on the edge cases and {} random cases\n", cases), true),
+                })
+            }
+        });
+    }
+
+    let selected: Vec<&dyn Solution> = aoc2024::solutions()
+        .filter(|s| args.all || Some(s.day()) == args.day)
+        .filter(|s| args.part.is_none_or(|part| s.part() == part))
+        .collect();
+    if selected.is_empty() {
+        return Err(match (args.day, args.part) {
+            (Some(day), Some(part)) => format!("no solution for day {} part {}", day, part),
+            (Some(day), None) => format!("no solution for day {}", day),
+            _ => "no solutions registered".to_string(),
+        });
+    }
+
+    let source = args.input.unwrap_or_else(InputSource::from_env);
+
+    let mut ok = true;
+    let mut current: Option<(u32, Option<String>)> = None;
+    for solution in selected {
+        let (day, part) = (solution.day(), solution.part());
+        if current.as_ref().map(|(d, _)| *d) != Some(day) {
+            let input = match source.load(day) {
+                Ok(input) => Some(input),
+                Err(err) => {
+                    eprintln!("d{}: cannot read input: {}", day, err);
+                    None
+                }
+            };
+            current = Some((day, input));
+        }
+        let Some(input) = current.as_ref().and_then(|(_, input)| input.as_ref()) else {
+            ok = false;
+            continue;
+        };
+
+        match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(input))) {
+            Ok(Ok(answer)) if args.all => println!("d{}_{}: {}", day, part, answer),
+            Ok(Ok(answer)) => println!("{}", answer),
+            Ok(Err(err)) => {
+                eprintln!("d{}_{}: {}", day, part, err);
+                ok = false;
+            }
+            Err(_) => {
+                eprintln!("d{}_{}: solver panicked", day, part);
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// Prints what `view` makes of the input for `day`. The view says whether
+/// what it found is a success.
+fn show(
+    day: u32,
+    input: Option<InputSource>,
+    view: impl FnOnce(&str) -> Result<(String, bool), AocError>,
+) -> Result<bool, String> {
+    let source = input.unwrap_or_else(InputSource::from_env);
+    let input = source.load(day).map_err(|err| format!("cannot read input: {}", err))?;
+    match view(&input) {
+        Ok((text, ok)) => {
+            print!("{}", text);
+            Ok(ok)
+        }
+        Err(err) => {
+            eprintln!("d{}: {}", day, err);
+            Ok(false)
+        }
+    }
+}
+
+/// Checks every solution against the answers file, returning whether none
+/// of them failed or produced a wrong answer.
+fn verify(args: &[String]) -> Result<bool, String> {
+    let mut path = PathBuf::from(DEFAULT_ANSWERS_FILE);
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--answers" => path = iter.next().ok_or("--answers needs a path")?.into(),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+
+    // Without an answers file every solution still runs, it just has
+    // nothing to be compared with
+    let answers = if path.exists() {
+        Answers::load(&path)?
+    } else {
+        eprintln!("{}: not found, no expected answers to compare with", path.display());
+        Answers::default()
+    };
+    let reports = verify::verify_all(aoc2024::solutions(), &InputSource::from_env(), &answers);
+
+    let color = io::stdout().is_terminal();
+    println!("day part  status   {:<20}  expected", "answer");
+    for report in &reports {
+        let status = format!("{:<7}", report.status);
+        let status = if color {
+            let code = match report.status {
+                Status::Pass => "32",
+                Status::Wrong | Status::Fail => "31",
+                Status::Missing => "33",
+            };
+            format!("\x1b[{}m{}\x1b[0m", code, status)
+        } else {
+            status
+        };
+        let answer = report
+            .answer
+            .as_deref()
+            .or(report.detail.as_deref())
+            .unwrap_or("-");
+        println!(
+            "{:>3} {:>4}  {}  {:<20}  {}",
+            report.day,
+            report.part,
+            status,
+            answer,
+            report.expected.as_deref().unwrap_or("-")
+        );
+    }
+
+    let count = |status| reports.iter().filter(|r| r.status == status).count();
+    println!(
+        "\n{} pass, {} wrong, {} fail, {} missing",
+        count(Status::Pass),
+        count(Status::Wrong),
+        count(Status::Fail),
+        count(Status::Missing)
+    );
+    Ok(count(Status::Wrong) == 0 && count(Status::Fail) == 0)
+}
+
+/// Times the selected solutions, printing a table and optionally saving it
+/// as JSON. Returns whether every solution could be timed.
+fn bench(args: &[String]) -> Result<bool, String> {
+    let mut options = bench::Options::default();
+    let mut json = None;
+    let (mut day, mut part) = (None, None);
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--runs" => {
+                let runs = iter.next().ok_or("--runs needs a count")?;
+                options.max_runs = runs.parse().map_err(|_| format!("invalid run count: {}", runs))?;
+            }
+            "--json" => json = Some(PathBuf::from(iter.next().ok_or("--json needs a path")?)),
+            _ if day.is_none() => day = Some(arg.parse::<u32>().map_err(|_| format!("invalid day: {}", arg))?),
+            _ if part.is_none() => part = Some(arg.parse::<u32>().map_err(|_| format!("invalid part: {}", arg))?),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+
+    let selected = aoc2024::solutions()
+        .filter(|s| day.is_none_or(|day| s.day() == day))
+        .filter(|s| part.is_none_or(|part| s.part() == part));
+    let timings = bench::bench_all(selected, &InputSource::from_env(), &options);
+    if timings.is_empty() {
+        return Err("no matching solutions".to_string());
+    }
+
+    println!("day part  {:>10}  {:>10}  {:>10}  runs", "parse", "solve", "total");
+    for timing in &timings {
+        match &timing.error {
+            Some(error) => println!("{:>3} {:>4}  {}", timing.day, timing.part, error),
+            None => println!(
+                "{:>3} {:>4}  {:>10}  {:>10}  {:>10}  {:>4}",
+                timing.day,
+                timing.part,
+                format_duration(timing.parse),
+                format_duration(timing.solve()),
+                format_duration(timing.total),
+                timing.runs
+            ),
+        }
+    }
+    let total: Duration = timings.iter().map(|t| t.total).sum();
+    println!("\ntotal {}", format_duration(total));
+
+    if let Some(path) = json {
+        fs::write(&path, bench::to_json(&timings)).map_err(|err| format!("{}: {}", path.display(), err))?;
+    }
+    Ok(timings.iter().all(|t| t.error.is_none()))
+}
@@ -1,58 +1,20 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashSet, VecDeque};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    row: i32,
-    col: i32,
-}
-
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect()
-        })
-        .collect()
-}
+// Height used for `.` cells, which no trail can step onto
+const IMPASSABLE: u32 = u32::MAX;
 
-fn find_trailheads(grid: &[Vec<u32>]) -> Vec<Point> {
-    let mut trailheads = Vec::new();
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
-
-    for row in 0..rows {
-        for col in 0..cols {
-            if grid[row as usize][col as usize] == 0 {
-                trailheads.push(Point { row, col });
-            }
-        }
-    }
-    trailheads
+fn parse_input(input: &str) -> Result<Grid<u32>, AocError> {
+    Grid::parse_with(input, |c| c.to_digit(10).unwrap_or(IMPASSABLE))
 }
 
-fn get_neighbors(point: Point, rows: i32, cols: i32) -> Vec<Point> {
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-    let mut neighbors = Vec::new();
-
-    for (dr, dc) in directions.iter() {
-        let new_row = point.row + dr;
-        let new_col = point.col + dc;
-
-        if new_row >= 0 && new_row < rows && new_col >= 0 && new_col < cols {
-            neighbors.push(Point {
-                row: new_row,
-                col: new_col,
-            });
-        }
-    }
-    neighbors
+fn find_trailheads(grid: &Grid<u32>) -> Vec<Point> {
+    grid.find_all(&0).collect()
 }
 
-fn calculate_trailhead_score(grid: &[Vec<u32>], start: Point) -> usize {
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
+fn calculate_trailhead_score(grid: &Grid<u32>, start: Point) -> usize {
     let mut reachable_nines = HashSet::new();
     let mut visited = HashSet::new();
     let mut queue = VecDeque::new();
@@ -62,12 +24,12 @@ fn calculate_trailhead_score(grid: &[Vec<u32>], start: Point) -> usize {
     visited.insert((start, 0));
 
     while let Some((current, height)) = queue.pop_front() {
-        if grid[current.row as usize][current.col as usize] == 9 {
+        if grid[current] == 9 {
             reachable_nines.insert(current);
         }
 
-        for neighbor in get_neighbors(current, rows, cols) {
-            let neighbor_height = grid[neighbor.row as usize][neighbor.col as usize];
+        for neighbor in grid.neighbors4(current) {
+            let neighbor_height = grid[neighbor];
             
             // Only proceed if it's exactly one higher than current height
             if neighbor_height == height + 1 {
@@ -83,21 +45,34 @@ fn calculate_trailhead_score(grid: &[Vec<u32>], start: Point) -> usize {
     reachable_nines.len()
 }
 
-fn solve(input: &str) -> usize {
-    let grid = parse_input(input);
+fn solve(input: &str) -> Result<usize, AocError> {
+    let grid = parse_input(input)?;
     let trailheads = find_trailheads(&grid);
     
-    trailheads
+    Ok(trailheads
         .iter()
         .map(|&start| calculate_trailhead_score(&grid, start))
-        .sum()
+        .sum())
 }
-#[test]
-fn main() {
-    let input = include_str!("d10.in");
 
-    let result = solve(input);
-    println!("Sum of trailhead scores: {}", result);
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -115,7 +90,7 @@ mod tests {
 01329801
 10456732";
         
-        assert_eq!(solve(input), 36);
+        assert_eq!(solve(input), Ok(36));
     }
 
     #[test]
@@ -125,7 +100,7 @@ mod tests {
 8765
 9876";
         
-        assert_eq!(solve(input), 1);
+        assert_eq!(solve(input), Ok(1));
     }
 
     #[test]
@@ -138,6 +113,6 @@ mod tests {
 ...9..2
 .....01";
 
-        assert_eq!(solve(input), 3);
+        assert_eq!(solve(input), Ok(3));
     }
-}
\ No newline at end of file
+}
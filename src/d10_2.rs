@@ -1,63 +1,27 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    row: i32,
-    col: i32,
-}
+// Height used for `.` cells, which no trail can step onto
+const IMPASSABLE: u32 = u32::MAX;
 
-fn parse_input(input: &str) -> Vec<Vec<u32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap())
-                .collect()
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Grid<u32>, AocError> {
+    Grid::parse_with(input, |c| c.to_digit(10).unwrap_or(IMPASSABLE))
 }
 
-fn find_trailheads(grid: &[Vec<u32>]) -> Vec<Point> {
-    let mut trailheads = Vec::new();
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
-
-    for row in 0..rows {
-        for col in 0..cols {
-            if grid[row as usize][col as usize] == 0 {
-                trailheads.push(Point { row, col });
-            }
-        }
-    }
-    trailheads
-}
-
-fn get_neighbors(point: Point, rows: i32, cols: i32) -> Vec<Point> {
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-    let mut neighbors = Vec::new();
-
-    for (dr, dc) in directions.iter() {
-        let new_row = point.row + dr;
-        let new_col = point.col + dc;
-
-        if new_row >= 0 && new_row < rows && new_col >= 0 && new_col < cols {
-            neighbors.push(Point {
-                row: new_row,
-                col: new_col,
-            });
-        }
-    }
-    neighbors
+fn find_trailheads(grid: &Grid<u32>) -> Vec<Point> {
+    grid.find_all(&0).collect()
 }
 
 fn count_paths_to_nine(
-    grid: &[Vec<u32>],
+    grid: &Grid<u32>,
     current: Point,
     target_height: u32,
     memo: &mut HashMap<(Point, u32), usize>,
 ) -> usize {
     // If we've reached height 9, we've found one valid path
-    if grid[current.row as usize][current.col as usize] == 9 {
+    if grid[current] == 9 {
         return 1;
     }
 
@@ -67,13 +31,11 @@ fn count_paths_to_nine(
         return count;
     }
 
-    let rows = grid.len() as i32;
-    let cols = grid[0].len() as i32;
     let mut total_paths = 0;
 
     // Try all possible next steps
-    for next in get_neighbors(current, rows, cols) {
-        let next_height = grid[next.row as usize][next.col as usize];
+    for next in grid.neighbors4(current) {
+        let next_height = grid[next];
 
         // Only proceed if the next position has exactly the height we're looking for
         if next_height == target_height {
@@ -86,26 +48,39 @@ fn count_paths_to_nine(
     total_paths
 }
 
-fn calculate_trailhead_rating(grid: &[Vec<u32>], start: Point) -> usize {
+fn calculate_trailhead_rating(grid: &Grid<u32>, start: Point) -> usize {
     let mut memo = HashMap::new();
     count_paths_to_nine(grid, start, 1, &mut memo)
 }
 
-fn solve(input: &str) -> usize {
-    let grid = parse_input(input);
+fn solve(input: &str) -> Result<usize, AocError> {
+    let grid = parse_input(input)?;
     let trailheads = find_trailheads(&grid);
 
-    trailheads
+    Ok(trailheads
         .iter()
         .map(|&start| calculate_trailhead_rating(&grid, start))
-        .sum()
+        .sum())
 }
-#[test]
-fn main() {
-    let input = include_str!("d10.in");
 
-    let result = solve(input);
-    println!("Sum of trailhead ratings: {}", result);
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -123,7 +98,7 @@ mod tests {
 01329801
 10456732";
 
-        assert_eq!(solve(input), 81);
+        assert_eq!(solve(input), Ok(81));
     }
 
     #[test]
@@ -136,7 +111,7 @@ mod tests {
 ..8765.
 ..9....";
 
-        assert_eq!(solve(input), 3);
+        assert_eq!(solve(input), Ok(3));
     }
 
     #[test]
@@ -149,7 +124,7 @@ mod tests {
 876....
 987....";
 
-        assert_eq!(solve(input), 13);
+        assert_eq!(solve(input), Ok(13));
     }
 
     #[test]
@@ -161,6 +136,6 @@ mod tests {
 4.6789
 56789.";
 
-        assert_eq!(solve(input), 227);
+        assert_eq!(solve(input), Ok(227));
     }
 }
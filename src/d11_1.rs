@@ -1,23 +1,28 @@
-#[test]
-fn main() {
-    // Example input (can be modified)
-    let initial_stones = include_str!("d11.in")
-        .split(" ")
-        .map(|s| s.parse().unwrap())
-        .collect();
-    let mut stones = initial_stones;
-    let blinks = 25;
-
-    // println!("Initial arrangement:");
-    // println!("{:?}", stones);
-
-    for i in 1..=blinks {
-        stones = transform_stones(&stones);
-        // println!("\nAfter {} blinks:", i);
-        // println!("{:?}", stones);
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part(&self) -> u32 {
+        1
     }
 
-    println!("\nFinal number of stones: {}", stones.len());
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut stones: Vec<u64> = non_empty_lines(input)
+            .flat_map(|line| line.text.split_whitespace().map(move |s| line.parse(s)))
+            .collect::<Result<_, _>>()?;
+        let blinks = 25;
+
+        for _ in 1..=blinks {
+            stones = transform_stones(&stones);
+        }
+
+        Ok(stones.len().into())
+    }
 }
 
 fn transform_stones(stones: &Vec<u64>) -> Vec<u64> {
@@ -39,8 +44,8 @@ fn transform_single_stone(stone: u64) -> Vec<u64> {
 
     // Rule 2: If stone has even number of digits, split in half
     let digit_count = count_digits(stone);
-    if digit_count % 2 == 0 {
-        let power = 10_u64.pow((digit_count / 2) as u32);
+    if digit_count.is_multiple_of(2) {
+        let power = 10_u64.pow(digit_count / 2);
         let left = stone / power;
         let right = stone % power;
         return vec![left, right];
@@ -1,3 +1,5 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
 
 fn solve(initial_stones: Vec<u64>, blinks: usize) -> usize {
@@ -29,17 +31,32 @@ fn solve(initial_stones: Vec<u64>, blinks: usize) -> usize {
 
     counts.values().sum()
 }
-#[test]
-fn main() {
-    let initial_stones = vec![125, 17];
-    println!("Part 1: {}", solve(initial_stones.clone(), 25));
-    println!("Part 2: {}", solve(initial_stones, 75));
-
-    // For the actual puzzle input:
-    let input: Vec<u64> = include_str!("d11.in")
-        .split(" ")
-        .map(|s| s.parse().unwrap())
-        .collect();
-    println!("Puzzle Part 1: {}", solve(input.clone(), 25));
-    println!("Puzzle Part 2: {}", solve(input, 75));
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let initial_stones: Vec<u64> = non_empty_lines(input)
+            .flat_map(|line| line.text.split_whitespace().map(move |s| line.parse(s)))
+            .collect::<Result<_, _>>()?;
+        Ok(solve(initial_stones, 75).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        assert_eq!(solve(vec![125, 17], 25), 55312);
+    }
 }
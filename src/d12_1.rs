@@ -1,9 +1,13 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashSet, VecDeque};
 
 #[derive(Debug)]
-struct Region {
-    area: usize,
-    perimeter: usize,
+pub(crate) struct Region {
+    pub(crate) area: usize,
+    pub(crate) perimeter: usize,
+    pub(crate) plots: HashSet<Point>,
 }
 
 impl Region {
@@ -12,78 +16,79 @@ impl Region {
     }
 }
 
-fn find_regions(grid: &Vec<Vec<char>>) -> Vec<Region> {
-    let rows = grid.len();
-    let cols = grid[0].len();
+pub(crate) fn find_regions(grid: &Grid<char>) -> Vec<Region> {
     let mut visited = HashSet::new();
     let mut regions = Vec::new();
 
-    // Directions for checking adjacent cells (up, right, down, left)
-    let directions = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-
-    for i in 0..rows {
-        for j in 0..cols {
-            if visited.contains(&(i, j)) {
-                continue;
-            }
+    for start in grid.points() {
+        if visited.contains(&start) {
+            continue;
+        }
 
-            let plant_type = grid[i][j];
-            let mut region = Region {
-                area: 0,
-                perimeter: 0,
-            };
-
-            // BFS to find all connected plots of the same type
-            let mut queue = VecDeque::new();
-            queue.push_back((i, j));
-            visited.insert((i, j));
-
-            while let Some((r, c)) = queue.pop_front() {
-                region.area += 1;
-
-                // Check all four directions
-                for (dr, dc) in directions.iter() {
-                    let new_r = (r as i32 + dr) as usize;
-                    let new_c = (c as i32 + dc) as usize;
-
-                    // Check if the new position is within bounds
-                    if new_r < rows && new_c < cols {
-                        if grid[new_r][new_c] == plant_type {
-                            // If it's the same plant type and not visited, add to queue
-                            if !visited.contains(&(new_r, new_c)) {
-                                queue.push_back((new_r, new_c));
-                                visited.insert((new_r, new_c));
-                            }
-                        } else {
-                            // If it's a different plant type, add to perimeter
-                            region.perimeter += 1;
-                        }
-                    } else {
-                        // If it's out of bounds, add to perimeter
-                        region.perimeter += 1;
+        let plant_type = grid[start];
+        let mut region = Region {
+            area: 0,
+            perimeter: 0,
+            plots: HashSet::new(),
+        };
+
+        // BFS to find all connected plots of the same type
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        visited.insert(start);
+
+        while let Some(plot) = queue.pop_front() {
+            region.area += 1;
+            region.plots.insert(plot);
+
+            // Every side not shared with a same-type neighbour is fence,
+            // including the sides along the edge of the map
+            let mut same = 0;
+            for next in grid.neighbors4(plot) {
+                if grid[next] == plant_type {
+                    same += 1;
+                    if visited.insert(next) {
+                        queue.push_back(next);
                     }
                 }
             }
-
-            regions.push(region);
+            region.perimeter += 4 - same;
         }
+
+        regions.push(region);
     }
 
     regions
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect()
+pub(crate) fn parse_input(input: &str) -> Result<Grid<char>, AocError> {
+    Grid::parse(input)
 }
 
-fn calculate_total_price(input: &str) -> usize {
-    let grid = parse_input(input);
+fn calculate_total_price(input: &str) -> Result<usize, AocError> {
+    let grid = parse_input(input)?;
     let regions = find_regions(&grid);
-    regions.iter().map(|region| region.price()).sum()
+    Ok(regions.iter().map(|region| region.price()).sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(calculate_total_price(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -96,7 +101,7 @@ mod tests {
 BBCD
 BBCC
 EEEC";
-        assert_eq!(calculate_total_price(input), 140);
+        assert_eq!(calculate_total_price(input), Ok(140));
     }
 
     #[test]
@@ -106,7 +111,7 @@ OXOXO
 OOOOO
 OXOXO
 OOOOO";
-        assert_eq!(calculate_total_price(input), 772);
+        assert_eq!(calculate_total_price(input), Ok(772));
     }
 
     #[test]
@@ -121,13 +126,6 @@ VVIIICJJEE
 MIIIIIJJEE
 MIIISIJEEE
 MMMISSJEEE";
-        assert_eq!(calculate_total_price(input), 1930);
+        assert_eq!(calculate_total_price(input), Ok(1930));
     }
 }
-#[test]
-fn main() {
-    // You can add your input here as a string
-    let input = include_str!("d12.in"); // Add your puzzle input here
-    let total_price = calculate_total_price(input);
-    println!("Total price of fencing: {}", total_price);
-}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::d12_1::{find_regions, parse_input, Region};
+use crate::geometry::{Direction, Point};
+use crate::{Answer, AocError, Solution};
+
+// A polygon has as many sides as corners, so count the corners of each plot.
+fn count_sides(region: &Region) -> usize {
+    let contains = |p: Point| region.plots.contains(&p);
+
+    let mut sides = 0;
+    for &plot in &region.plots {
+        // Each direction and the next one clockwise form one potential
+        // corner of a plot
+        for direction in Direction::ALL {
+            let (d1, d2) = (direction.delta(), direction.turn_right().delta());
+            let side1 = contains(plot + d1);
+            let side2 = contains(plot + d2);
+            let diagonal = contains(plot + d1 + d2);
+
+            // Outer corner: both neighbours are outside the region
+            if !side1 && !side2 {
+                sides += 1;
+            }
+            // Inner corner: both neighbours are inside but the diagonal is not
+            if side1 && side2 && !diagonal {
+                sides += 1;
+            }
+        }
+    }
+    sides
+}
+
+fn calculate_total_price(input: &str) -> Result<usize, AocError> {
+    let grid = parse_input(input)?;
+    let regions = find_regions(&grid);
+    Ok(regions
+        .iter()
+        .map(|region| region.area * count_sides(region))
+        .sum())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(calculate_total_price(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_example() {
+        let input = "AAAA
+BBCD
+BBCC
+EEEC";
+        assert_eq!(calculate_total_price(input), Ok(80));
+    }
+
+    #[test]
+    fn test_o_x_example() {
+        let input = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+        assert_eq!(calculate_total_price(input), Ok(436));
+    }
+
+    #[test]
+    fn test_e_shape() {
+        let input = "EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE";
+        assert_eq!(calculate_total_price(input), Ok(236));
+    }
+
+    #[test]
+    fn test_diagonal_touching_regions() {
+        let input = "AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA";
+        assert_eq!(calculate_total_price(input), Ok(368));
+    }
+
+    #[test]
+    fn test_larger_example() {
+        let input = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+        assert_eq!(calculate_total_price(input), Ok(1206));
+    }
+}
@@ -1,45 +1,63 @@
-use std::error::Error;
+use crate::error::{non_empty_lines, sections, Line};
+use crate::{Answer, AocError, Solution};
 use std::str::FromStr;
 
 #[derive(Debug)]
-struct ClawMachine {
-    button_a: (i64, i64),  // (X, Y) movement for button A
-    button_b: (i64, i64),  // (X, Y) movement for button B
-    prize: (i64, i64),     // (X, Y) coordinates of prize
+pub(crate) struct ClawMachine {
+    pub(crate) button_a: (i64, i64),  // (X, Y) movement for button A
+    pub(crate) button_b: (i64, i64),  // (X, Y) movement for button B
+    pub(crate) prize: (i64, i64),     // (X, Y) coordinates of prize
 }
 
-impl FromStr for ClawMachine {
-    type Err = Box<dyn Error>;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<&str> = s.lines().collect();
-        if lines.len() != 3 {
-            return Err("Invalid input format".into());
-        }
+impl ClawMachine {
+    // A machine is three lines: both buttons, then the prize.
+    fn parse(lines: &[Line]) -> Result<Self, AocError> {
+        let [a, b, prize] = lines else {
+            let line = lines.first().map_or(1, |line| line.number);
+            return Err(AocError::parse(line, format!("expected 3 lines per machine, got {}", lines.len())));
+        };
 
-        // Parse Button A
-        let a_parts: Vec<&str> = lines[0].split(", ").collect();
-        let ax = a_parts[0].split("+").nth(1).ok_or("Invalid X format")?.parse::<i64>()?;
-        let ay = a_parts[1].split("+").nth(1).ok_or("Invalid Y format")?.parse::<i64>()?;
+        Ok(ClawMachine {
+            button_a: parse_pair(a, "Button A: ", '+')?,
+            button_b: parse_pair(b, "Button B: ", '+')?,
+            prize: parse_pair(prize, "Prize: ", '=')?,
+        })
+    }
+}
 
-        // Parse Button B
-        let b_parts: Vec<&str> = lines[1].split(", ").collect();
-        let bx = b_parts[0].split("+").nth(1).ok_or("Invalid X format")?.parse::<i64>()?;
-        let by = b_parts[1].split("+").nth(1).ok_or("Invalid Y format")?.parse::<i64>()?;
+// Parses lines like `Button A: X+94, Y+34` into `(94, 34)`.
+fn parse_pair(line: &Line, prefix: &str, sign: char) -> Result<(i64, i64), AocError> {
+    let rest = line
+        .text
+        .strip_prefix(prefix)
+        .ok_or_else(|| line.error(format!("expected a line starting with {:?}", prefix)))?;
+    let (x, y) = rest
+        .split_once(", ")
+        .ok_or_else(|| line.error_at(rest, "expected X and Y separated by \", \""))?;
+    let x = x
+        .strip_prefix('X')
+        .and_then(|x| x.strip_prefix(sign))
+        .ok_or_else(|| line.error_at(x, format!("expected X{}", sign)))?;
+    let y = y
+        .strip_prefix('Y')
+        .and_then(|y| y.strip_prefix(sign))
+        .ok_or_else(|| line.error_at(y, format!("expected Y{}", sign)))?;
+    Ok((line.parse(x)?, line.parse(y)?))
+}
 
-        // Parse Prize coordinates
-        let p_parts: Vec<&str> = lines[2].split(", ").collect();
-        let px = p_parts[0].split("=").nth(1).ok_or("Invalid X format")?.parse::<i64>()?;
-        let py = p_parts[1].split("=").nth(1).ok_or("Invalid Y format")?.parse::<i64>()?;
+impl FromStr for ClawMachine {
+    type Err = AocError;
 
-        Ok(ClawMachine {
-            button_a: (ax, ay),
-            button_b: (bx, by),
-            prize: (px, py),
-        })
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ClawMachine::parse(&non_empty_lines(s).collect::<Vec<_>>())
     }
 }
 
+/// Every machine in the input, separated by blank lines.
+pub(crate) fn parse_machines(input: &str) -> Result<Vec<ClawMachine>, AocError> {
+    sections(input).iter().map(|lines| ClawMachine::parse(lines)).collect()
+}
+
 fn solve_machine(machine: &ClawMachine) -> Option<i64> {
     // Try all combinations of button presses up to 100 each
     for a in 0..=100 {
@@ -56,25 +74,33 @@ fn solve_machine(machine: &ClawMachine) -> Option<i64> {
     None
 }
 
-fn solve_puzzle(input: &str) -> i64 {
-    let machines: Vec<ClawMachine> = input
-        .split("\n\n")
-        .filter(|s| !s.trim().is_empty())
-        .filter_map(|s| s.parse().ok())
-        .collect();
+fn solve_puzzle(input: &str) -> Result<i64, AocError> {
+    let machines = parse_machines(input)?;
 
-    machines
+    Ok(machines
         .iter()
         .filter_map(solve_machine)
-        .sum()
+        .sum())
 }
-#[test]
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = include_str!("d13.in");
 
-    let result = solve_puzzle(input);
-    println!("Minimum tokens needed: {}", result);
-    Ok(())
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve_puzzle(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_machines(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -99,7 +125,7 @@ Button A: X+69, Y+23
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279";
 
-        assert_eq!(solve_puzzle(input), 480);
+        assert_eq!(solve_puzzle(input), Ok(480));
     }
 
     #[test]
@@ -111,4 +137,21 @@ Prize: X=8400, Y=5400";
         let machine: ClawMachine = input.parse().unwrap();
         assert_eq!(solve_machine(&machine), Some(280));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+
+Button A: X+26, Y+66
+Button B: X+67, Y-21
+Prize: X=12748, Y=12176";
+
+        assert_eq!(solve_puzzle(input), Err(AocError::parse(1, "expected 3 lines per machine, got 2")));
+        let (_, second) = input.split_once("\n\n").unwrap();
+        assert_eq!(
+            second.parse::<ClawMachine>().unwrap_err().to_string(),
+            "line 2, column 17: expected Y+"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::d13_1::{parse_machines, ClawMachine};
+use crate::{Answer, AocError, Solution};
+
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+// The two buttons give a 2x2 linear system; solve it with Cramer's rule and
+// accept only non-negative integer press counts.
+fn solve_machine(machine: &ClawMachine, offset: i64) -> Result<Option<i64>, AocError> {
+    let overflow = || AocError::InvalidInput(format!("the machine with a prize at {:?} overflows i64", machine.prize));
+    let mul = |a: i64, b: i64| a.checked_mul(b).ok_or_else(overflow);
+    let cross = |a: i64, b: i64, c: i64, d: i64| mul(a, b)?.checked_sub(mul(c, d)?).ok_or_else(overflow);
+
+    let (ax, ay) = machine.button_a;
+    let (bx, by) = machine.button_b;
+    let px = machine.prize.0.checked_add(offset).ok_or_else(overflow)?;
+    let py = machine.prize.1.checked_add(offset).ok_or_else(overflow)?;
+
+    let det = cross(ax, by, ay, bx)?;
+    if det == 0 {
+        // Parallel buttons never show up in the puzzle input
+        return Ok(None);
+    }
+
+    let a_num = cross(px, by, py, bx)?;
+    let b_num = cross(ax, py, ay, px)?;
+    if a_num % det != 0 || b_num % det != 0 {
+        return Ok(None);
+    }
+
+    let (a, b) = (a_num / det, b_num / det);
+    if a < 0 || b < 0 {
+        return Ok(None);
+    }
+    mul(3, a)?.checked_add(b).map(Some).ok_or_else(overflow)
+}
+
+fn solve_puzzle(input: &str, offset: i64) -> Result<i64, AocError> {
+    let machines = parse_machines(input)?;
+
+    let mut tokens: i64 = 0;
+    for machine in &machines {
+        if let Some(cost) = solve_machine(machine, offset)? {
+            tokens = tokens
+                .checked_add(cost)
+                .ok_or_else(|| AocError::InvalidInput("the token total doesn't fit an i64".to_string()))?;
+        }
+    }
+    Ok(tokens)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve_puzzle(input, PRIZE_OFFSET)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_machines(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn test_without_offset_matches_part1() {
+        assert_eq!(solve_puzzle(EXAMPLE, 0), Ok(480));
+    }
+
+    #[test]
+    fn test_with_offset() {
+        assert_eq!(solve_puzzle(EXAMPLE, PRIZE_OFFSET), Ok(875318608908));
+    }
+
+    #[test]
+    fn test_only_second_and_fourth_machines_win() {
+        let machines = parse_machines(EXAMPLE).unwrap();
+        let wins: Vec<bool> = machines
+            .iter()
+            .map(|m| solve_machine(m, PRIZE_OFFSET).unwrap().is_some())
+            .collect();
+        assert_eq!(wins, vec![false, true, false, true]);
+    }
+    #[test]
+    fn test_overflow() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400";
+        assert!(solve_puzzle(input, i64::MAX).is_err());
+        assert!(solve_puzzle(input, i64::MAX / 10).is_err());
+    }
+}
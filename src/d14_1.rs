@@ -1,53 +1,39 @@
+use crate::error::{non_empty_lines, Line};
+use crate::geometry::Point;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Velocity {
-    x: i32,
-    y: i32,
-}
-
 #[derive(Debug)]
 struct Robot {
-    position: Position,
-    velocity: Velocity,
+    position: Point,
+    velocity: Point,
 }
 
 impl Robot {
-    fn from_str(s: &str) -> Option<Self> {
-        let parts: Vec<&str> = s.split(' ').collect();
-        if parts.len() != 2 {
-            return None;
-        }
-
-        let pos_parts: Vec<&str> = parts[0].trim_start_matches("p=").split(',').collect();
-        let vel_parts: Vec<&str> = parts[1].trim_start_matches("v=").split(',').collect();
-
-        Some(Robot {
-            position: Position {
-                x: pos_parts[0].parse().ok()?,
-                y: pos_parts[1].parse().ok()?,
-            },
-            velocity: Velocity {
-                x: vel_parts[0].parse().ok()?,
-                y: vel_parts[1].parse().ok()?,
-            },
+    // Parses lines like `p=0,4 v=3,-3`.
+    fn parse(line: Line) -> Result<Self, AocError> {
+        let (position, velocity) = line.split_once(" ")?;
+        Ok(Robot {
+            position: parse_vector(&line, position, "p=")?,
+            velocity: parse_vector(&line, velocity, "v=")?,
         })
     }
 
-    fn update_position(&mut self, width: i32, height: i32) {
-        // Update position
-        self.position.x = (self.position.x + self.velocity.x).rem_euclid(width);
-        self.position.y = (self.position.y + self.velocity.y).rem_euclid(height);
+    fn update_position(&mut self, width: i64, height: i64) {
+        // Robots wrap around the edges of the room
+        self.position = (self.position + self.velocity).rem_euclid(width, height);
     }
 }
 
-fn get_quadrant(pos: Position, width: i32, height: i32) -> Option<usize> {
+fn parse_vector(line: &Line, text: &str, prefix: &str) -> Result<Point, AocError> {
+    let (x, y) = text
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(|| line.error_at(text, format!("expected {}X,Y", prefix)))?;
+    Ok(Point::new(line.parse(x)?, line.parse(y)?))
+}
+
+fn get_quadrant(pos: Point, width: i64, height: i64) -> Option<usize> {
     let mid_x = width / 2;
     let mid_y = height / 2;
 
@@ -65,11 +51,8 @@ fn get_quadrant(pos: Position, width: i32, height: i32) -> Option<usize> {
     })
 }
 
-fn simulate_robots(input: &str, width: i32, height: i32, steps: i32) -> i64 {
-    let mut robots: Vec<Robot> = input
-        .lines()
-        .filter_map(|line| Robot::from_str(line.trim()))
-        .collect();
+fn simulate_robots(input: &str, width: i64, height: i64, steps: i32) -> Result<i64, AocError> {
+    let mut robots: Vec<Robot> = non_empty_lines(input).map(Robot::parse).collect::<Result<_, _>>()?;
 
     // Simulate movement
     for _ in 0..steps {
@@ -79,7 +62,7 @@ fn simulate_robots(input: &str, width: i32, height: i32, steps: i32) -> i64 {
     }
 
     // Count robots in each quadrant
-    let mut quadrant_counts = vec![0; 4];
+    let mut quadrant_counts = [0; 4];
     for robot in robots {
         if let Some(quadrant) = get_quadrant(robot.position, width, height) {
             quadrant_counts[quadrant] += 1;
@@ -87,16 +70,56 @@ fn simulate_robots(input: &str, width: i32, height: i32, steps: i32) -> i64 {
     }
 
     // Calculate safety factor (multiply all quadrant counts)
-    quadrant_counts.iter().fold(1, |acc, &count| acc * count as i64)
+    Ok(quadrant_counts.iter().fold(1, |acc, &count| acc * count as i64))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        14
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let width = 101;
+        let height = 103;
+        let steps = 100;
+
+        Ok(simulate_robots(input, width, height, steps)?.into())
+    }
 }
-#[test]
-fn main() {
-    let input = include_str!("d14.in");
 
-    let width = 101;
-    let height = 103;
-    let steps = 100;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    #[test]
+    fn test_example() {
+        assert_eq!(simulate_robots(EXAMPLE, 11, 7, 100), Ok(12));
+    }
 
-    let safety_factor = simulate_robots(input, width, height, steps);
-    println!("Safety factor after {} seconds: {}", steps, safety_factor);
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            simulate_robots("p=0,4 v=3,-3\np=6,3 w=-1,-3", 11, 7, 100),
+            Err(AocError::parse_at(2, 7, "expected v=X,Y"))
+        );
+    }
+}
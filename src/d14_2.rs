@@ -1,59 +1,39 @@
+use crate::error::{non_empty_lines, Line};
+use crate::geometry::Point;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy)]
-struct Position {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Velocity {
-    x: i32,
-    y: i32,
-}
-
 #[derive(Debug)]
 struct Robot {
-    position: Position,
-    velocity: Velocity,
+    position: Point,
+    velocity: Point,
 }
 
 impl Robot {
-    fn from_str(s: &str) -> Option<Self> {
-        let parts: Vec<&str> = s.split(' ').collect();
-        if parts.len() != 2 {
-            return None;
-        }
-
-        let pos_parts: Vec<&str> = parts[0].trim_start_matches("p=").split(',').collect();
-        let vel_parts: Vec<&str> = parts[1].trim_start_matches("v=").split(',').collect();
-
-        Some(Robot {
-            position: Position {
-                x: pos_parts[0].parse().ok()?,
-                y: pos_parts[1].parse().ok()?,
-            },
-            velocity: Velocity {
-                x: vel_parts[0].parse().ok()?,
-                y: vel_parts[1].parse().ok()?,
-            },
+    // Parses lines like `p=0,4 v=3,-3`.
+    fn parse(line: Line) -> Result<Self, AocError> {
+        let (position, velocity) = line.split_once(" ")?;
+        Ok(Robot {
+            position: parse_vector(&line, position, "p=")?,
+            velocity: parse_vector(&line, velocity, "v=")?,
         })
     }
 
-    fn update_position(&mut self, width: i32, height: i32) {
-        // Update position
-        self.position.x = (self.position.x + self.velocity.x).rem_euclid(width);
-        self.position.y = (self.position.y + self.velocity.y).rem_euclid(height);
+    fn update_position(&mut self, width: i64, height: i64) {
+        // Robots wrap around the edges of the room
+        self.position = (self.position + self.velocity).rem_euclid(width, height);
     }
 }
-fn print(arr: [[&str; 103]; 103]) {
-    for row in arr {
-        let s: String = row.join("");
-        println!("{}", s);
-    }
+
+fn parse_vector(line: &Line, text: &str, prefix: &str) -> Result<Point, AocError> {
+    let (x, y) = text
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.split_once(','))
+        .ok_or_else(|| line.error_at(text, format!("expected {}X,Y", prefix)))?;
+    Ok(Point::new(line.parse(x)?, line.parse(y)?))
 }
 
-fn get_quadrant(pos: Position, width: i32, height: i32) -> Option<usize> {
+fn get_quadrant(pos: Point, width: i64, height: i64) -> Option<usize> {
     let mid_x = width / 2;
     let mid_y = height / 2;
 
@@ -71,11 +51,8 @@ fn get_quadrant(pos: Position, width: i32, height: i32) -> Option<usize> {
     })
 }
 
-fn simulate_robots(input: &str, width: i32, height: i32, steps: i64) -> i64 {
-    let mut robots: Vec<Robot> = input
-        .lines()
-        .filter_map(|line| Robot::from_str(line.trim()))
-        .collect();
+fn simulate_robots(input: &str, width: i64, height: i64, steps: i64) -> Result<i64, AocError> {
+    let mut robots: Vec<Robot> = non_empty_lines(input).map(Robot::parse).collect::<Result<_, _>>()?;
 
     // Simulate movement
     for step in 1..steps {
@@ -90,22 +67,29 @@ fn simulate_robots(input: &str, width: i32, height: i32, steps: i64) -> i64 {
         for row in pos.iter() {
             let s = row.join("");
             if s.contains("#############################") {
-                print(pos);
-                return step;
+                return Ok(step);
             }
         }
     }
-    return -1;
+    Err(AocError::NoSolution)
 }
 
-#[test]
-fn main() {
-    let input = include_str!("d14.in");
+pub struct Solver;
 
-    let width = 101;
-    let height = 103;
-    let steps = 10000;
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        14
+    }
 
-    let safety_factor = simulate_robots(input, width, height, steps);
-    println!("form a tree {} seconds: {}", steps, safety_factor);
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let width = 101;
+        let height = 103;
+        let steps = 10000;
+
+        Ok(simulate_robots(input, width, height, steps)?.into())
+    }
 }
@@ -1,181 +1,94 @@
-
-fn solve() -> i32 {
-    let input = include_str!("d15.in");
-    let (map_str, moves_str) = input.split_once("\n\n").unwrap();
-    let mut map: Vec<Vec<char>> = map_str.lines().map(|line| line.chars().collect()).collect();
-    let moves: Vec<char> = moves_str.chars().filter(|c| !c.is_whitespace()).collect();
-
-    let mut robot_pos:(i32,i32) = (0, 0);
-    for r in 0..map.len() {
-        for c in 0..map[0].len() {
-            if map[r][c] == '@' {
-                robot_pos = (r as i32, c as i32);
-                break;
-            }
+use crate::error::lines;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
+
+/// Splits the input into the warehouse map and the robot's moves.
+pub(crate) fn parse_input(input: &str) -> Result<(&str, Vec<Direction>), AocError> {
+    let (map_str, moves_str) = input.split_once("\n\n").ok_or_else(|| {
+        AocError::InvalidInput("expected the map and the moves separated by a blank line".to_string())
+    })?;
+
+    // The move list is wrapped over several lines, starting after the blank one
+    let first_line = map_str.lines().count() + 1;
+    let mut moves = Vec::new();
+    for line in lines(moves_str) {
+        for (i, c) in line.text.chars().enumerate() {
+            let direction = Direction::try_from(c)
+                .map_err(|c| AocError::parse_at(first_line + line.number, i + 1, format!("invalid move {:?}", c)))?;
+            moves.push(direction);
         }
     }
+    Ok((map_str, moves))
+}
 
-    let rows = map.len() as i32;
-    let cols = map[0].len() as i32;
-
-    for move_cmd in moves {
-        let (r_rob, c_rob) = robot_pos;
-        let (dr, dc) = match move_cmd {
-            '^' => (-1, 0),
-            'v' => (1, 0),
-            '<' => (0, -1),
-            '>' => (0, 1),
-            _ => continue,
-        };
-
-        let new_r_rob = r_rob + dr;
-        let new_c_rob = c_rob + dc;
-
-        if new_r_rob < 0 || new_r_rob >= rows || new_c_rob < 0 || new_c_rob >= cols || map[new_r_rob as usize][new_c_rob as usize] == '#' {
-            continue;
-        }
-
-        if map[new_r_rob as usize][new_c_rob as usize] == 'O' {
-            let mut push_r = new_r_rob;
-            let mut push_c = new_c_rob;
-
-            let mut can_push = true;
-            let mut boxes_to_move = Vec::new();
-            while push_r >= 0 && push_r < rows && push_c >= 0 && push_c < cols && map[push_r as usize][push_c as usize] == 'O' {
-                boxes_to_move.push((push_r, push_c));
-                push_r += dr;
-                push_c += dc;
-            }
+fn solve(input: &str) -> Result<i32, AocError> {
+    let (map_str, moves) = parse_input(input)?;
+    solve_with_input(map_str, &moves)
+}
 
-            if push_r < 0 || push_r >= rows || push_c < 0 || push_c >= cols || map[push_r as usize][push_c as usize] == '#' {
-                can_push = false;
-            }
+pub struct Solver;
 
-            if can_push {
-                // Move the boxes
-                for i in (1..boxes_to_move.len()).rev() {
-                    map[boxes_to_move[i].0 as usize + dr as usize][boxes_to_move[i].1 as usize + dc as usize] = 'O';
-                }
-                map[boxes_to_move[0].0 as usize + dr as usize][boxes_to_move[0].1 as usize + dc as usize] = 'O';
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        15
+    }
 
-                // Move the robot
-                map[new_r_rob as usize][new_c_rob as usize] = '@';
-                map[r_rob as usize][c_rob as usize] = '.';
-                robot_pos = (new_r_rob, new_c_rob);
-            }
-        } else if map[new_r_rob as usize][new_c_rob as usize] == '.' {
-            map[new_r_rob as usize][new_c_rob as usize] = '@';
-            map[r_rob as usize][c_rob as usize] = '.';
-            robot_pos = (new_r_rob, new_c_rob);
-        }
+    fn part(&self) -> u32 {
+        1
     }
 
-    let mut gps_sum = 0;
-    for r in 0..rows {
-        for c in 0..cols {
-            if map[r as usize][c as usize] == 'O' {
-                gps_sum += 100 * r + c;
-            }
-        }
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
     }
 
-    gps_sum
-}
-#[test]
-fn main() {
-    println!("{}", solve());
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[test]
 fn test_small_example() {
     let map_str = "########\n#..O.O.#\n##@.O..#\n#...O..#\n#.#.O..#\n#...O..#\n#......#";
     let moves_str = "<^^>>>vv<v>>v<<";
-    assert_eq!(solve_with_input(map_str, moves_str), 2028);
+    assert_eq!(solve(&format!("{}\n\n{}", map_str, moves_str)), Ok(2028));
 }
 
-fn solve_with_input(map_str: &str, moves_str: &str) -> i32 {
-    let mut map: Vec<Vec<char>> = map_str.lines().map(|line| line.chars().collect()).collect();
-    let moves: Vec<char> = moves_str.chars().collect();
+#[test]
+fn test_invalid_move() {
+    let input = "#####\n#@O.#\n#####\n\n<>\n>x<";
+    assert_eq!(solve(input), Err(AocError::parse_at(6, 2, "invalid move 'x'")));
+}
 
-    let mut robot_pos = (0, 0);
-    for r in 0..map.len() {
-        for c in 0..map[0].len() {
-            if map[r][c] == '@' {
-                robot_pos = (r, c);
-                break;
-            }
+fn solve_with_input(map_str: &str, moves: &[Direction]) -> Result<i32, AocError> {
+    let mut map = Grid::parse(map_str)?;
+    let mut robot_pos = map
+        .find(&'@')
+        .ok_or_else(|| AocError::InvalidInput("no robot on the map".to_string()))?;
+
+    for &direction in moves {
+        // Skip over the row of boxes in front of the robot, if any
+        let new_robot_pos = robot_pos + direction;
+        let mut push_pos = new_robot_pos;
+        while map.get(push_pos) == Some(&'O') {
+            push_pos += direction;
         }
-    }
-
-    let rows = map.len();
-    let cols = map[0].len();
-
-    for move_cmd in moves {
-        let (r_rob, c_rob) = robot_pos;
-        let (dr, dc):(i32,i32) = match move_cmd {
-            '^' => (-1, 0),
-            'v' => (1, 0),
-            '<' => (0, -1),
-            '>' => (0, 1),
-            _ => continue,
-        };
-
-        let new_r_rob = r_rob + dr as usize;
-        let new_c_rob = c_rob + dc as usize;
 
-        if new_r_rob < 0 || new_r_rob >= rows || new_c_rob < 0 || new_c_rob >= cols || map[new_r_rob][new_c_rob] == '#' {
+        // Anything but free space behind the boxes (or right in front of the
+        // robot) blocks the move, including the edge of the map
+        if map.get(push_pos) != Some(&'.') {
             continue;
         }
 
-        if map[new_r_rob][new_c_rob] == 'O' {
-            let mut push_r = new_r_rob as i32;
-            let mut push_c = new_c_rob as i32;
-
-            let mut can_push = true;
-            let mut boxes_to_move = Vec::new();
-            while push_r >= 0 && push_r < rows as i32 && push_c >= 0 && push_c < cols as i32 && map[push_r as usize][push_c as usize] == 'O' {
-                boxes_to_move.push((push_r, push_c));
-                push_r += dr;
-                push_c += dc;
-            }
-
-            if push_r < 0 || push_r >= rows as i32 || push_c < 0 || push_c >= cols as i32 || map[push_r as usize][push_c as usize] == '#' {
-                can_push = false;
-            }
-
-            if can_push {
-                // Move the boxes
-                for i in (0..boxes_to_move.len()).rev() {
-                    let (box_r, box_c) = boxes_to_move[i];
-                    let new_box_r = box_r + dr;
-                    let new_box_c = box_c + dc;
-                    if new_box_r >= 0 && new_box_r < rows as i32 && new_box_c >= 0 && new_box_c < cols as i32 {
-                        map[new_box_r  as usize][new_box_c  as usize] = 'O';
-                        if i == 0 {
-                            map[box_r  as usize][box_c  as usize] = '@';
-                            map[r_rob  as usize][c_rob  as usize] = '.';
-                            robot_pos = (new_r_rob, new_c_rob);
-                        } else {
-                            map[box_r  as usize][box_c  as usize] = '.';
-                        }
-                    }
-                }
-            }
-        } else if map[new_r_rob][new_c_rob] == '.' {
-            map[new_r_rob][new_c_rob] = '@';
-            map[r_rob][c_rob] = '.';
-            robot_pos = (new_r_rob, new_c_rob);
+        // Moving a row of boxes along one cell is the same as moving the
+        // first box to the free cell at the end
+        if push_pos != new_robot_pos {
+            map[push_pos] = 'O';
         }
+        map[new_robot_pos] = '@';
+        map[robot_pos] = '.';
+        robot_pos = new_robot_pos;
     }
 
-    let mut gps_sum:i32 = 0;
-    for r in 0..rows {
-        for c in 0..cols {
-            if map[r][c] == 'O' {
-                gps_sum += 100 * r as i32 + c as i32;
-            }
-        }
-    }
-
-    gps_sum
-}
\ No newline at end of file
+    Ok(map.find_all(&'O').map(|p| 100 * p.y as i32 + p.x as i32).sum())
+}
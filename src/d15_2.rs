@@ -0,0 +1,182 @@
+use crate::d15_1::parse_input;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
+use std::collections::{HashSet, VecDeque};
+
+// Everything except the robot is twice as wide: `O` becomes `[]`.
+fn widen_map(map_str: &str) -> Result<Grid<char>, AocError> {
+    let wide: String = map_str
+        .chars()
+        .flat_map(|c| match c {
+            '\n' => vec!['\n'],
+            '#' => vec!['#', '#'],
+            'O' => vec!['[', ']'],
+            '@' => vec!['@', '.'],
+            _ => vec!['.', '.'],
+        })
+        .collect();
+    Grid::parse(&wide)
+}
+
+// Collects every cell that has to move along with the robot, or `None` if
+// something in the chain is blocked by a wall.
+fn cells_to_push(map: &Grid<char>, robot: Point, direction: Direction) -> Option<Vec<Point>> {
+    let vertical = matches!(direction, Direction::Up | Direction::Down);
+    let mut cells = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(robot);
+    seen.insert(robot);
+
+    while let Some(cell) = queue.pop_front() {
+        cells.push(cell);
+
+        let next = cell + direction;
+
+        let mut push = |cell: Point| {
+            if seen.insert(cell) {
+                queue.push_back(cell);
+            }
+        };
+
+        match map.get(next).copied().unwrap_or('#') {
+            '#' => return None,
+            '[' => {
+                push(next);
+                // Pushing a box vertically drags its other half along
+                if vertical {
+                    push(next + Direction::Right);
+                }
+            }
+            ']' => {
+                push(next);
+                if vertical {
+                    push(next + Direction::Left);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(cells)
+}
+
+fn simulate(map: &mut Grid<char>, moves: &[Direction]) -> Result<(), AocError> {
+    let mut robot = map
+        .find(&'@')
+        .ok_or_else(|| AocError::InvalidInput("no robot on the map".to_string()))?;
+
+    for &direction in moves {
+        let Some(cells) = cells_to_push(map, robot, direction) else {
+            continue;
+        };
+
+        // Lift everything up first so overlapping moves can't clobber each other
+        let contents: Vec<char> = cells.iter().map(|&p| map[p]).collect();
+        for &p in &cells {
+            map[p] = '.';
+        }
+        for (&p, &cell) in cells.iter().zip(&contents) {
+            map[p + direction] = cell;
+        }
+
+        robot += direction;
+    }
+    Ok(())
+}
+
+fn gps_sum(map: &Grid<char>) -> usize {
+    map.find_all(&'[').map(|p| 100 * p.y as usize + p.x as usize).sum()
+}
+
+fn solve(input: &str) -> Result<usize, AocError> {
+    let (map_str, moves) = parse_input(input)?;
+    let mut map = widen_map(map_str)?;
+    simulate(&mut map, &moves)?;
+    Ok(gps_sum(&map))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widen() {
+        let map = widen_map("#.O@").unwrap();
+        assert_eq!(map.to_string(), "##..[]@.");
+    }
+
+    #[test]
+    fn test_small_example() {
+        let mut map = widen_map(
+            "#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######",
+        )
+        .unwrap();
+        let moves: Vec<Direction> = "<vv<<^^<<^^".chars().map(|c| Direction::try_from(c).unwrap()).collect();
+        simulate(&mut map, &moves).unwrap();
+        assert_eq!(
+            map.to_string(),
+            "##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############"
+        );
+        assert_eq!(gps_sum(&map), 105 + 207 + 306);
+    }
+
+    #[test]
+    fn test_larger_example() {
+        let input = "##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+        assert_eq!(solve(input), Ok(9021));
+    }
+}
@@ -1,200 +1,82 @@
-use std::collections::{HashMap, BinaryHeap};
-use std::cmp::Ordering;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::search::astar;
+use crate::{Answer, AocError, Solution};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Position {
-    x: i32,
-    y: i32,
-}
+// Where the reindeer is and which way it faces.
+pub(crate) type State = (Point, Direction);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
+#[derive(Debug)]
+pub(crate) struct Maze {
+    grid: Grid<char>,
+    pub(crate) start: Point,
+    pub(crate) end: Point,
 }
 
-impl Direction {
-    fn turn_left(&self) -> Direction {
-        match self {
-            Direction::North => Direction::West,
-            Direction::East => Direction::North,
-            Direction::South => Direction::East,
-            Direction::West => Direction::South,
-        }
+impl Maze {
+    pub(crate) fn parse(input: &str) -> Result<Self, AocError> {
+        let grid = Grid::parse(input)?;
+        let find = |tile: char| {
+            grid.find(&tile)
+                .ok_or_else(|| AocError::InvalidInput(format!("no {} tile in the maze", tile)))
+        };
+        let (start, end) = (find('S')?, find('E')?);
+        Ok(Maze { grid, start, end })
     }
 
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
+    pub(crate) fn is_wall(&self, pos: Point) -> bool {
+        // Anything off the map is as good as a wall
+        self.grid.get(pos).is_none_or(|&cell| cell == '#')
     }
 
-    fn move_forward(&self, pos: Position) -> Position {
-        match self {
-            Direction::North => Position { x: pos.x, y: pos.y - 1 },
-            Direction::East => Position { x: pos.x + 1, y: pos.y },
-            Direction::South => Position { x: pos.x, y: pos.y + 1 },
-            Direction::West => Position { x: pos.x - 1, y: pos.y },
+    // Stepping forward costs 1 and turning on the spot costs 1000.
+    pub(crate) fn successors(&self, &(position, direction): &State) -> Vec<(State, u64)> {
+        let mut next = vec![
+            ((position, direction.turn_left()), 1000),
+            ((position, direction.turn_right()), 1000),
+        ];
+        if !self.is_wall(position + direction) {
+            next.push(((position + direction, direction), 1));
         }
+        next
     }
-}
-
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
-struct State {
-    cost: i32,
-    position: Position,
-    direction: Direction,
-}
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
+    // The reindeer starts facing East
+    pub(crate) fn start_state(&self) -> State {
+        (self.start, Direction::Right)
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn solve(&self) -> Option<u64> {
+        let path = astar(
+            self.start_state(),
+            |state| self.successors(state),
+            // Every remaining tile costs at least one step
+            |&(position, _)| position.manhattan(self.end) as u64,
+            |&(position, _)| position == self.end,
+        )?;
+        Some(path.cost)
     }
 }
 
-#[derive(Debug)]
-struct Maze {
-    grid: Vec<Vec<char>>,
-    start: Position,
-    end: Position,
-}
+pub struct Solver;
 
-impl Maze {
-    fn parse(input: &str) -> Self {
-        let grid: Vec<Vec<char>> = input.lines()
-            .map(|line| line.chars().collect())
-            .collect();
-        
-        let mut start = Position { x: 0, y: 0 };
-        let mut end = Position { x: 0, y: 0 };
-        
-        for (y, row) in grid.iter().enumerate() {
-            for (x, &cell) in row.iter().enumerate() {
-                match cell {
-                    'S' => start = Position { x: x as i32, y: y as i32 },
-                    'E' => end = Position { x: x as i32, y: y as i32 },
-                    _ => {}
-                }
-            }
-        }
-        
-        Maze { grid, start, end }
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        16
     }
 
-    fn is_wall(&self, pos: Position) -> bool {
-        if pos.x < 0 || pos.y < 0 {
-            return true;
-        }
-        
-        let y = pos.y as usize;
-        let x = pos.x as usize;
-        
-        if y >= self.grid.len() || x >= self.grid[0].len() {
-            return true;
-        }
-        
-        self.grid[y][x] == '#'
+    fn part(&self) -> u32 {
+        1
     }
 
-    fn solve(&self) -> Option<i32> {
-        let mut heap = BinaryHeap::new();
-        let mut seen = HashMap::new();
-        
-        // Start facing East as specified
-        let initial = State {
-            cost: 0,
-            position: self.start,
-            direction: Direction::East,
-        };
-        
-        heap.push(initial);
-        seen.insert((initial.position, initial.direction), 0);
-        
-        while let Some(State { cost, position, direction }) = heap.pop() {
-            if position == self.end {
-                return Some(cost);
-            }
-            
-            // Try moving forward (cost: 1)
-            let next_pos = direction.move_forward(position);
-            if !self.is_wall(next_pos) {
-                let next = State {
-                    cost: cost + 1,
-                    position: next_pos,
-                    direction,
-                };
-                
-                if let Some(&prev_cost) = seen.get(&(next_pos, direction)) {
-                    if next.cost < prev_cost {
-                        heap.push(next);
-                        seen.insert((next_pos, direction), next.cost);
-                    }
-                } else {
-                    heap.push(next);
-                    seen.insert((next_pos, direction), next.cost);
-                }
-            }
-            
-            // Try turning left (cost: 1000)
-            let left = direction.turn_left();
-            let next = State {
-                cost: cost + 1000,
-                position,
-                direction: left,
-            };
-            
-            if let Some(&prev_cost) = seen.get(&(position, left)) {
-                if next.cost < prev_cost {
-                    heap.push(next);
-                    seen.insert((position, left), next.cost);
-                }
-            } else {
-                heap.push(next);
-                seen.insert((position, left), next.cost);
-            }
-            
-            // Try turning right (cost: 1000)
-            let right = direction.turn_right();
-            let next = State {
-                cost: cost + 1000,
-                position,
-                direction: right,
-            };
-            
-            if let Some(&prev_cost) = seen.get(&(position, right)) {
-                if next.cost < prev_cost {
-                    heap.push(next);
-                    seen.insert((position, right), next.cost);
-                }
-            } else {
-                heap.push(next);
-                seen.insert((position, right), next.cost);
-            }
-        }
-        
-        None
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let maze = Maze::parse(input)?;
+        let score = maze.solve().ok_or(AocError::NoSolution)?;
+        Ok(score.into())
     }
-}
-#[test]
-fn main() {
-    let input = include_str!("d16.in");
-
-    let maze = Maze::parse(input);
-    if let Some(result) = maze.solve() {
-        println!("Lowest possible score: {}", result);
-    } else {
-        println!("No solution found!");
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        Maze::parse(input).map(drop)
     }
 }
 
@@ -220,7 +102,7 @@ mod tests {
 #S..#.....#...#
 ###############";
 
-        let maze = Maze::parse(input);
+        let maze = Maze::parse(input).unwrap();
         assert_eq!(maze.solve(), Some(7036));
     }
 
@@ -244,7 +126,7 @@ mod tests {
 #S#.............#
 #################";
 
-        let maze = Maze::parse(input);
+        let maze = Maze::parse(input).unwrap();
         assert_eq!(maze.solve(), Some(11048));
     }
-}
\ No newline at end of file
+}
@@ -1,14 +1,305 @@
-struct Computer {
-    register_a: i64,
-    register_b: i64,
-    register_c: i64,
+use crate::bigint::BigUint;
+use crate::error::{non_empty_lines, Line};
+use crate::{Answer, AocError, Solution};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// A register value the VM can compute with.
+///
+/// Registers are unsigned, and shifting by at least the type's width gives
+/// zero instead of overflowing, so any program runs without panicking.
+pub(crate) trait Word: Clone + PartialEq + fmt::Debug + fmt::Display + FromStr {
+    fn from_u64(n: u64) -> Self;
+    fn is_zero(&self) -> bool;
+    /// The value modulo 8.
+    fn low_bits(&self) -> i64;
+    fn saturating_shr(&self, amount: &Self) -> Self;
+    fn xor(&self, other: &Self) -> Self;
+}
+
+macro_rules! word {
+    ($($t:ty),*) => {
+        $(
+            impl Word for $t {
+                fn from_u64(n: u64) -> Self {
+                    n as _
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0
+                }
+
+                fn low_bits(&self) -> i64 {
+                    (*self % 8) as i64
+                }
+
+                fn saturating_shr(&self, amount: &Self) -> Self {
+                    u32::try_from(*amount).ok().and_then(|n| self.checked_shr(n)).unwrap_or(0)
+                }
+
+                fn xor(&self, other: &Self) -> Self {
+                    self ^ other
+                }
+            }
+        )*
+    };
+}
+
+word!(u64, u128);
+
+impl Word for BigUint {
+    fn from_u64(n: u64) -> Self {
+        n.into()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn low_bits(&self) -> i64 {
+        (self.low_u64() % 8) as i64
+    }
+
+    fn saturating_shr(&self, amount: &Self) -> Self {
+        // Anything too big for a u64 shifts every bit out anyway
+        self >> amount.to_u64().unwrap_or(u64::MAX)
+    }
+
+    fn xor(&self, other: &Self) -> Self {
+        self ^ other
+    }
+}
+
+/// A combo operand: a literal 0-3 or one of the registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Combo {
+    Literal(i64),
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Combo::Literal(n) => write!(f, "{}", n),
+            Combo::A => write!(f, "A"),
+            Combo::B => write!(f, "B"),
+            Combo::C => write!(f, "C"),
+        }
+    }
+}
+
+/// One decoded opcode/operand pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Instruction {
+    Adv(Combo),
+    Bxl(i64),
+    Bst(Combo),
+    Jnz(usize),
+    Bxc,
+    Out(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+}
+
+impl Instruction {
+    /// Decodes the instruction at `ip`, which is only used for error messages.
+    pub(crate) fn decode(opcode: i64, operand: i64, ip: usize) -> Result<Self, AocError> {
+        let combo = || match operand {
+            0..=3 => Ok(Combo::Literal(operand)),
+            4 => Ok(Combo::A),
+            5 => Ok(Combo::B),
+            6 => Ok(Combo::C),
+            _ => Err(AocError::InvalidState(format!(
+                "invalid combo operand {} at instruction pointer {}",
+                operand, ip
+            ))),
+        };
+        Ok(match opcode {
+            0 => Instruction::Adv(combo()?),
+            1 => Instruction::Bxl(operand),
+            2 => Instruction::Bst(combo()?),
+            3 => Instruction::Jnz(operand as usize),
+            4 => Instruction::Bxc,
+            5 => Instruction::Out(combo()?),
+            6 => Instruction::Bdv(combo()?),
+            7 => Instruction::Cdv(combo()?),
+            _ => {
+                return Err(AocError::InvalidState(format!(
+                    "invalid opcode {} at instruction pointer {}",
+                    opcode, ip
+                )))
+            }
+        })
+    }
+
+    /// The instruction as a statement, e.g. `C = A >> B` for `cdv B`.
+    pub(crate) fn symbolic(&self) -> String {
+        match self {
+            // Dividing by 2^x is the same as shifting right by x
+            Instruction::Adv(x) => format!("A = A >> {}", x),
+            Instruction::Bxl(n) => format!("B ^= {}", n),
+            Instruction::Bst(x) => format!("B = {} % 8", x),
+            Instruction::Jnz(target) => format!("if A != 0 goto {}", target),
+            Instruction::Bxc => "B ^= C".to_string(),
+            Instruction::Out(x) => format!("out {} % 8", x),
+            Instruction::Bdv(x) => format!("B = A >> {}", x),
+            Instruction::Cdv(x) => format!("C = A >> {}", x),
+        }
+    }
+}
+
+/// Prints the mnemonic form, e.g. `bst A`.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Adv(x) => write!(f, "adv {}", x),
+            Instruction::Bxl(n) => write!(f, "bxl {}", n),
+            Instruction::Bst(x) => write!(f, "bst {}", x),
+            Instruction::Jnz(target) => write!(f, "jnz {}", target),
+            Instruction::Bxc => write!(f, "bxc"),
+            Instruction::Out(x) => write!(f, "out {}", x),
+            Instruction::Bdv(x) => write!(f, "bdv {}", x),
+            Instruction::Cdv(x) => write!(f, "cdv {}", x),
+        }
+    }
+}
+
+/// Decodes every instruction in `program`, in order.
+pub(crate) fn disassemble(program: &[i64]) -> Result<Vec<Instruction>, AocError> {
+    program
+        .chunks(2)
+        .enumerate()
+        .map(|(i, pair)| match *pair {
+            [opcode, operand] => Instruction::decode(opcode, operand, 2 * i),
+            _ => Err(AocError::InvalidState(format!(
+                "missing operand at instruction pointer {}",
+                2 * i
+            ))),
+        })
+        .collect()
+}
+
+/// Renders `program` as one statement per line.
+///
+/// Puzzle programs are a single loop whose only jump is the `jnz 0` at the
+/// end; those print as a `loop:` label followed by the loop body. Anything
+/// else gets an `L<ip>:` label in front of every jump target.
+pub(crate) fn pretty_print(program: &[i64]) -> Result<String, AocError> {
+    let instructions = disassemble(program)?;
+    let mut out = String::new();
+
+    if let Some((Instruction::Jnz(0), body)) = instructions.split_last() {
+        if !body.iter().any(|i| matches!(i, Instruction::Jnz(_))) {
+            out += "loop:\n";
+            for instruction in body {
+                out += &format!("    {};\n", instruction.symbolic());
+            }
+            out += "    if A != 0 goto loop;\n";
+            return Ok(out);
+        }
+    }
+
+    let targets: HashSet<usize> = instructions
+        .iter()
+        .filter_map(|i| match i {
+            Instruction::Jnz(target) => Some(*target),
+            _ => None,
+        })
+        .collect();
+    for (i, instruction) in instructions.iter().enumerate() {
+        if targets.contains(&(2 * i)) {
+            out += &format!("L{}:\n", 2 * i);
+        }
+        let statement = match instruction {
+            Instruction::Jnz(target) => format!("if A != 0 goto L{}", target),
+            _ => instruction.symbolic(),
+        };
+        out += &format!("    {};\n", statement);
+    }
+    Ok(out)
+}
+
+/// One of the VM's three registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Register {
+    A,
+    B,
+    C,
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Register::A => "A",
+            Register::B => "B",
+            Register::C => "C",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What one call to [`Computer::step`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Step<W = u64> {
+    /// Where the instruction was read from.
+    pub(crate) ip: usize,
+    pub(crate) instruction: Instruction,
+    /// Every register the instruction changed, with its old and new value.
+    pub(crate) changes: Vec<(Register, W, W)>,
+    pub(crate) output: Option<i64>,
+}
+
+/// One line per step, e.g. `   4  cdv B    C: 0 -> 5`, so traces diff cleanly.
+impl<W: Word> fmt::Display for Step<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>4}  {:<7}", self.ip, self.instruction.to_string())?;
+        for (register, before, after) in &self.changes {
+            write!(f, "  {}: {} -> {}", register, before, after)?;
+        }
+        if let Some(value) = self.output {
+            write!(f, "  out: {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why [`Computer::resume`] gave control back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stop {
+    /// The instruction pointer ran off the end of the program.
+    Halted,
+    /// The next instruction is at this breakpoint.
+    Breakpoint(usize),
+    /// The instruction budget is used up.
+    OutOfBudget,
+}
+
+/// Instructions a new [`Computer`] may run before giving up, far more than
+/// any puzzle program needs but enough to stop one that never halts.
+pub(crate) const DEFAULT_BUDGET: u64 = 1_000_000;
+
+pub(crate) struct Computer<W = u64> {
+    pub(crate) register_a: W,
+    pub(crate) register_b: W,
+    pub(crate) register_c: W,
     instruction_pointer: usize,
-    program: Vec<i64>,
-    outputs: Vec<i64>,
+    pub(crate) program: Vec<i64>,
+    pub(crate) outputs: Vec<i64>,
+    /// Instruction pointers that [`Computer::resume`] stops in front of.
+    pub(crate) breakpoints: HashSet<usize>,
+    /// How many instructions may run in total, if limited. Starts out as
+    /// [`DEFAULT_BUDGET`].
+    pub(crate) budget: Option<u64>,
+    /// Every step taken so far, if tracing is on.
+    pub(crate) trace: Option<Vec<Step<W>>>,
+    steps: u64,
 }
 
-impl Computer {
-    fn new(program: Vec<i64>, reg_a: i64, reg_b: i64, reg_c: i64) -> Self {
+impl<W: Word> Computer<W> {
+    pub(crate) fn new(program: Vec<i64>, reg_a: W, reg_b: W, reg_c: W) -> Self {
         Computer {
             register_a: reg_a,
             register_b: reg_b,
@@ -16,68 +307,123 @@ impl Computer {
             instruction_pointer: 0,
             program,
             outputs: Vec::new(),
+            breakpoints: HashSet::new(),
+            budget: Some(DEFAULT_BUDGET),
+            trace: None,
+            steps: 0,
         }
     }
 
-    fn get_combo_operand(&self, operand: i64) -> i64 {
+    /// Starts recording every step into [`Computer::trace`].
+    pub(crate) fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    pub(crate) fn instruction_pointer(&self) -> usize {
+        self.instruction_pointer
+    }
+
+    fn combo(&self, operand: Combo) -> W {
         match operand {
-            0..=3 => operand,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            _ => panic!("Invalid combo operand: {}", operand),
+            Combo::Literal(n) => W::from_u64(n as u64),
+            Combo::A => self.register_a.clone(),
+            Combo::B => self.register_b.clone(),
+            Combo::C => self.register_c.clone(),
         }
     }
 
-    fn run(&mut self) {
-        while self.instruction_pointer < self.program.len() {
-            let opcode = self.program[self.instruction_pointer];
-            let operand = self.program[self.instruction_pointer + 1];
+    fn registers(&self) -> [W; 3] {
+        [self.register_a.clone(), self.register_b.clone(), self.register_c.clone()]
+    }
 
-            match opcode {
-                0 => {
-                    // adv
-                    let power = self.get_combo_operand(operand);
-                    self.register_a /= 2_i64.pow(power as u32);
-                }
-                1 => {
-                    // bxl
-                    self.register_b ^= operand;
-                }
-                2 => {
-                    // bst
-                    self.register_b = self.get_combo_operand(operand) % 8;
-                }
-                3 => {
-                    // jnz
-                    if self.register_a != 0 {
-                        self.instruction_pointer = operand as usize;
-                        continue;
-                    }
-                }
-                4 => {
-                    // bxc
-                    self.register_b ^= self.register_c;
-                }
-                5 => {
-                    // out
-                    let value = self.get_combo_operand(operand) % 8;
-                    self.outputs.push(value);
-                }
-                6 => {
-                    // bdv
-                    let power = self.get_combo_operand(operand);
-                    self.register_b = self.register_a / 2_i64.pow(power as u32);
-                }
-                7 => {
-                    // cdv
-                    let power = self.get_combo_operand(operand);
-                    self.register_c = self.register_a / 2_i64.pow(power as u32);
+    /// Executes the next instruction, or returns `None` once halted.
+    pub(crate) fn step(&mut self) -> Result<Option<Step<W>>, AocError> {
+        let ip = self.instruction_pointer;
+        if ip >= self.program.len() {
+            return Ok(None);
+        }
+        let opcode = self.program[ip];
+        let Some(&operand) = self.program.get(ip + 1) else {
+            return Err(AocError::InvalidState(format!(
+                "missing operand at instruction pointer {}",
+                ip
+            )));
+        };
+
+        let instruction = Instruction::decode(opcode, operand, ip)?;
+        let before = self.registers();
+        let mut output = None;
+        self.instruction_pointer += 2;
+        // Dividing by 2^x is a right shift, which saturates to zero
+        match instruction {
+            Instruction::Adv(x) => self.register_a = self.register_a.saturating_shr(&self.combo(x)),
+            Instruction::Bxl(n) => self.register_b = self.register_b.xor(&W::from_u64(n as u64)),
+            Instruction::Bst(x) => self.register_b = W::from_u64(self.combo(x).low_bits() as u64),
+            Instruction::Jnz(target) => {
+                if !self.register_a.is_zero() {
+                    self.instruction_pointer = target;
                 }
-                _ => panic!("Invalid opcode: {}", opcode),
             }
+            Instruction::Bxc => self.register_b = self.register_b.xor(&self.register_c),
+            Instruction::Out(x) => {
+                let value = self.combo(x).low_bits();
+                self.outputs.push(value);
+                output = Some(value);
+            }
+            Instruction::Bdv(x) => self.register_b = self.register_a.saturating_shr(&self.combo(x)),
+            Instruction::Cdv(x) => self.register_c = self.register_a.saturating_shr(&self.combo(x)),
+        }
+        self.steps += 1;
+
+        let changes = [Register::A, Register::B, Register::C]
+            .into_iter()
+            .zip(before.into_iter().zip(self.registers()))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(register, (before, after))| (register, before, after))
+            .collect();
+        let step = Step {
+            ip,
+            instruction,
+            changes,
+            output,
+        };
+        if let Some(trace) = &mut self.trace {
+            trace.push(step.clone());
+        }
+        Ok(Some(step))
+    }
+
+    /// Runs until the program halts, reaches a breakpoint or uses up its
+    /// budget. Always takes at least one step, so calling it again continues
+    /// past the breakpoint it stopped at.
+    pub(crate) fn resume(&mut self) -> Result<Stop, AocError> {
+        loop {
+            if self.budget.is_some_and(|budget| self.steps >= budget) {
+                return Ok(Stop::OutOfBudget);
+            }
+            if self.step()?.is_none() {
+                return Ok(Stop::Halted);
+            }
+            if self.breakpoints.contains(&self.instruction_pointer) {
+                return Ok(Stop::Breakpoint(self.instruction_pointer));
+            }
+        }
+    }
 
-            self.instruction_pointer += 2;
+    /// Runs the program to completion, ignoring breakpoints.
+    pub(crate) fn run(&mut self) -> Result<(), AocError> {
+        loop {
+            match self.resume()? {
+                Stop::Halted => return Ok(()),
+                Stop::Breakpoint(_) => continue,
+                Stop::OutOfBudget => {
+                    return Err(AocError::InvalidState(format!(
+                        "instruction budget of {} used up at instruction pointer {}",
+                        self.steps, self.instruction_pointer
+                    )))
+                }
+            }
         }
     }
 
@@ -89,36 +435,137 @@ impl Computer {
             .join(",")
     }
 }
-#[test]
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = include_str!("d17.in");
-    // Example from the problem
-    let program = input
-        .split("\n")
-        .skip_while(|p| !p.starts_with("Program: "))
-        .next()
-        .unwrap()
-        .strip_prefix("Program: ")
-        .unwrap()
-        .split(",")
-        .map(|x| x.parse::<i64>().unwrap())
-        .collect();
-    let mut computer = Computer::new(
-        program,
-        input
-            .split("\n")
-            .next()
-            .unwrap()
-            .strip_prefix("Register A: ")
-            .unwrap()
-            .parse()
-            .unwrap(),
-        0,
-        0,
-    );
-    computer.run();
-    println!("Output: {}", computer.get_output());
-    Ok(())
+
+/// A program decoded once up front, for running it many times on `u64`
+/// registers without the decoding, tracing and budget checks of
+/// [`Computer::step`]. It produces the same output and errors as
+/// [`Computer::run`], but nothing stops a program that never halts.
+pub(crate) struct Compiled {
+    // The instruction starting at every address, including the odd ones a
+    // jump could land on. Bad opcodes and operands only fail once reached.
+    ops: Vec<Result<Instruction, AocError>>,
+}
+
+impl Compiled {
+    pub(crate) fn new(program: &[i64]) -> Self {
+        let ops = (0..program.len())
+            .map(|ip| match program.get(ip + 1) {
+                Some(&operand) => Instruction::decode(program[ip], operand, ip),
+                None => Err(AocError::InvalidState(format!(
+                    "missing operand at instruction pointer {}",
+                    ip
+                ))),
+            })
+            .collect();
+        Compiled { ops }
+    }
+
+    /// Runs the program from the start, appending what it outputs.
+    pub(crate) fn run(&self, registers: [u64; 3], outputs: &mut Vec<i64>) -> Result<(), AocError> {
+        let [mut a, mut b, mut c] = registers;
+        let shr = |value: u64, amount: u64| u32::try_from(amount).ok().and_then(|n| value.checked_shr(n)).unwrap_or(0);
+        let mut ip = 0;
+        while let Some(op) = self.ops.get(ip) {
+            let instruction = match op {
+                Ok(instruction) => *instruction,
+                Err(err) => return Err(err.clone()),
+            };
+            let combo = |x: Combo| match x {
+                Combo::Literal(n) => n as u64,
+                Combo::A => a,
+                Combo::B => b,
+                Combo::C => c,
+            };
+            match instruction {
+                Instruction::Adv(x) => a = shr(a, combo(x)),
+                Instruction::Bxl(n) => b ^= n as u64,
+                Instruction::Bst(x) => b = combo(x) % 8,
+                Instruction::Jnz(target) if a != 0 => {
+                    ip = target;
+                    continue;
+                }
+                Instruction::Jnz(_) => {}
+                Instruction::Bxc => b ^= c,
+                Instruction::Out(x) => outputs.push((combo(x) % 8) as i64),
+                Instruction::Bdv(x) => b = shr(a, combo(x)),
+                Instruction::Cdv(x) => c = shr(a, combo(x)),
+            }
+            ip += 2;
+        }
+        Ok(())
+    }
+}
+
+/// Index of the first step where two traces take a different instruction or
+/// output a different value, or `None` if they agree until one of them ends.
+/// Register values are ignored, since runs with different inputs always
+/// differ there.
+pub(crate) fn first_divergence<W: Word>(left: &[Step<W>], right: &[Step<W>]) -> Option<usize> {
+    let key = |step: &Step<W>| (step.ip, step.instruction, step.output);
+    left.iter().zip(right).position(|(l, r)| key(l) != key(r))
+}
+
+/// Reads the registers and program, with registers of any [`Word`] type.
+pub(crate) fn parse_input<W: Word>(input: &str) -> Result<Computer<W>, AocError> {
+    let mut registers = [None, None, None];
+    let mut program = None;
+
+    for line in non_empty_lines(input) {
+        let (field, value) = line.split_once(":")?;
+        match field {
+            "Register A" => registers[0] = Some(line.parse(value)?),
+            "Register B" => registers[1] = Some(line.parse(value)?),
+            "Register C" => registers[2] = Some(line.parse(value)?),
+            "Program" => program = Some(parse_program(&line, value)?),
+            _ => return Err(line.error_at(field, format!("unknown field {:?}", field))),
+        }
+    }
+
+    let missing = |name: &str| AocError::InvalidInput(format!("missing {}", name));
+    let [a, b, c] = registers;
+    Ok(Computer::new(
+        program.ok_or_else(|| missing("Program"))?,
+        a.ok_or_else(|| missing("Register A"))?,
+        b.ok_or_else(|| missing("Register B"))?,
+        c.ok_or_else(|| missing("Register C"))?,
+    ))
+}
+
+// Every opcode and operand is a 3-bit number.
+fn parse_program(line: &Line, text: &str) -> Result<Vec<i64>, AocError> {
+    text.split(',')
+        .enumerate()
+        .map(|(i, word)| {
+            let value = line.parse(word)?;
+            if !(0..8).contains(&value) {
+                let kind = if i % 2 == 0 { "opcode" } else { "operand" };
+                return Err(line.error(format!("invalid {} {}", kind, value)));
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut computer: Computer = parse_input(input)?;
+        computer.run()?;
+        Ok(computer.get_output().into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input::<u64>(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -128,31 +575,243 @@ mod tests {
     #[test]
     fn test_example_program() {
         let program = vec![0, 1, 5, 4, 3, 0];
-        let mut computer = Computer::new(program, 729, 0, 0);
-        computer.run();
+        let mut computer = Computer::<u64>::new(program, 729, 0, 0);
+        computer.run().unwrap();
         assert_eq!(computer.get_output(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn test_other_examples() {
         // Test case: If register C contains 9, the program 2,6 would set register B to 1
-        let mut computer = Computer::new(vec![2, 6], 0, 0, 9);
-        computer.run();
+        let mut computer = Computer::<u64>::new(vec![2, 6], 0, 0, 9);
+        computer.run().unwrap();
         assert_eq!(computer.register_b, 1);
 
         // Test case: If register A contains 10, the program 5,0,5,1,5,4 would output 0,1,2
-        let mut computer = Computer::new(vec![5, 0, 5, 1, 5, 4], 10, 0, 0);
-        computer.run();
+        let mut computer = Computer::<u64>::new(vec![5, 0, 5, 1, 5, 4], 10, 0, 0);
+        computer.run().unwrap();
         assert_eq!(computer.get_output(), "0,1,2");
 
         // Test case: If register B contains 29, the program 1,7 would set register B to 26
-        let mut computer = Computer::new(vec![1, 7], 0, 29, 0);
-        computer.run();
+        let mut computer = Computer::<u64>::new(vec![1, 7], 0, 29, 0);
+        computer.run().unwrap();
         assert_eq!(computer.register_b, 26);
 
         // Test case: If register B contains 2024 and register C contains 43690, the program 4,0 would set register B to 44354
-        let mut computer = Computer::new(vec![4, 0], 0, 2024, 43690);
-        computer.run();
+        let mut computer = Computer::<u64>::new(vec![4, 0], 0, 2024, 43690);
+        computer.run().unwrap();
         assert_eq!(computer.register_b, 44354);
     }
+
+    #[test]
+    fn test_parse_input() {
+        let input = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0
+";
+        let mut computer: Computer = parse_input(input).unwrap();
+        computer.run().unwrap();
+        assert_eq!(computer.get_output(), "4,6,3,5,6,3,5,2,1,0");
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,9,4";
+        assert_eq!(parse_input::<u64>(input).err().unwrap().to_string(), "line 5: invalid opcode 9");
+        assert!(parse_input::<u64>("Register A: x").is_err());
+        assert_eq!(
+            parse_input::<u64>("Register A: 1\nRegister B: 0\nProgram: 0,1").err(),
+            Some(AocError::InvalidInput("missing Register C".to_string()))
+        );
+
+        let mut computer = Computer::<u64>::new(vec![5, 7], 0, 0, 0);
+        assert_eq!(
+            computer.run().unwrap_err().to_string(),
+            "invalid combo operand 7 at instruction pointer 0"
+        );
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = [2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0];
+        let mnemonics: Vec<String> = disassemble(&program).unwrap().iter().map(|i| i.to_string()).collect();
+        assert_eq!(
+            mnemonics,
+            ["bst A", "bxl 3", "cdv B", "adv 3", "bxl 5", "bxc", "out B", "jnz 0"]
+        );
+        assert_eq!(
+            disassemble(&[0, 7]).unwrap_err().to_string(),
+            "invalid combo operand 7 at instruction pointer 0"
+        );
+        assert_eq!(
+            disassemble(&[0, 1, 5]).unwrap_err().to_string(),
+            "missing operand at instruction pointer 2"
+        );
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let program = [2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0];
+        assert_eq!(
+            pretty_print(&program).unwrap(),
+            "loop:
+    B = A % 8;
+    B ^= 3;
+    C = A >> B;
+    A = A >> 3;
+    B ^= 5;
+    B ^= C;
+    out B % 8;
+    if A != 0 goto loop;
+"
+        );
+
+        // Not a single loop back to the start, so jump targets get labels
+        assert_eq!(
+            pretty_print(&[5, 4, 0, 1, 3, 2]).unwrap(),
+            "    out A % 8;
+L2:
+    A = A >> 1;
+    if A != 0 goto L2;
+"
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let mut computer = Computer::<u64>::new(vec![0, 1, 5, 4, 3, 0], 6, 0, 0);
+        let step = computer.step().unwrap().unwrap();
+        assert_eq!(step.ip, 0);
+        assert_eq!(step.instruction, Instruction::Adv(Combo::Literal(1)));
+        assert_eq!(step.changes, [(Register::A, 6, 3)]);
+        assert_eq!(step.to_string(), "   0  adv 1    A: 6 -> 3");
+
+        let step = computer.step().unwrap().unwrap();
+        assert_eq!((step.changes.len(), step.output), (0, Some(3)));
+        assert_eq!(computer.instruction_pointer(), 4);
+
+        // Run the jump, both remaining iterations and then halt
+        while computer.step().unwrap().is_some() {}
+        assert_eq!(computer.get_output(), "3,1,0");
+        assert_eq!(computer.step(), Ok(None));
+    }
+
+    #[test]
+    fn test_breakpoints_and_budget() {
+        let mut computer = Computer::<u64>::new(vec![0, 1, 5, 4, 3, 0], 729, 0, 0);
+        computer.breakpoints.insert(2);
+        assert_eq!(computer.resume(), Ok(Stop::Breakpoint(2)));
+        assert_eq!(computer.register_a, 364);
+        assert_eq!(computer.resume(), Ok(Stop::Breakpoint(2)));
+        assert_eq!(computer.get_output(), "4");
+
+        // Jumping back to the start forever
+        let mut computer = Computer::<u64>::new(vec![1, 0, 3, 0], 1, 0, 0);
+        computer.budget = Some(100);
+        assert_eq!(computer.resume(), Ok(Stop::OutOfBudget));
+        assert_eq!(
+            computer.run().unwrap_err().to_string(),
+            "instruction budget of 100 used up at instruction pointer 0"
+        );
+
+        // The solver gives up on it too instead of hanging
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0";
+        assert_eq!(
+            Solver.solve(input),
+            Err(AocError::InvalidState(format!(
+                "instruction budget of {} used up at instruction pointer 0",
+                DEFAULT_BUDGET
+            )))
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let program = vec![2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0];
+        let trace = |a| {
+            let mut computer = Computer::<u64>::new(program.clone(), a, 0, 0).with_trace();
+            computer.run().unwrap();
+            computer.trace.unwrap()
+        };
+
+        let (one, two) = (trace(0o17), trace(0o13));
+        assert_eq!(one.len(), 16);
+        assert_eq!(first_divergence(&one, &one), None);
+        // Both runs take the same path, but the first output differs
+        assert_eq!(first_divergence(&one, &two), Some(6));
+        assert_eq!(one[6].to_string(), "  12  out B    out: 1");
+        assert_eq!(two[6].output, Some(6));
+    }
+
+    #[test]
+    fn test_wide_registers() {
+        // A shift by a register value well past 64 bits: `bdv A`, `out B`
+        let program = vec![6, 4, 5, 5];
+        let mut computer = Computer::<u64>::new(program.clone(), 1000, 7, 0);
+        computer.run().unwrap();
+        assert_eq!((computer.register_b, computer.get_output()), (0, "0".to_string()));
+
+        // 2^127 >> 126 keeps a bit that u64 could never hold
+        let mut computer = Computer::<u128>::new(vec![0, 5, 5, 4], 1 << 127, 126, 0);
+        computer.run().unwrap();
+        assert_eq!((computer.register_a, computer.get_output()), (2, "2".to_string()));
+
+        let input = "Register A: 1606938044258990275541962092341162602522202993782792835301376
+Register B: 199
+Register C: 0
+
+Program: 0,5,5,4";
+        let mut computer: Computer<BigUint> = parse_input(input).unwrap();
+        computer.run().unwrap();
+        assert_eq!(computer.register_a, BigUint::from(2u64));
+        assert_eq!(computer.get_output(), "2");
+    }
+
+    #[test]
+    fn test_every_instruction_is_safe() {
+        // Every opcode with every operand, on registers near the top of the range
+        for opcode in 0..8 {
+            for operand in 0..8 {
+                let program = vec![opcode, operand, 5, operand];
+                let mut computer = Computer::<u64>::new(program.clone(), u64::MAX, u64::MAX - 1, 64);
+                computer.budget = Some(100);
+                let _ = computer.run();
+
+                let big = &BigUint::from(u128::MAX) << 100;
+                let mut computer = Computer::new(program, big.clone(), big, BigUint::from(1u64 << 40));
+                computer.budget = Some(100);
+                let _ = computer.run();
+            }
+        }
+    }
+
+    #[test]
+    fn test_compiled_matches_computer() {
+        let programs: [&[i64]; 5] = [
+            &[0, 1, 5, 4, 3, 0],
+            &[2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 0, 3, 5, 5, 3, 0],
+            // Jumps to an odd address, running an operand as an opcode
+            &[0, 3, 3, 5, 5, 0, 3, 0],
+            // Fails once the bad combo operand is reached
+            &[5, 4, 0, 7],
+            &[0, 3, 5, 4, 3],
+        ];
+        for program in programs {
+            let compiled = Compiled::new(program);
+            for a in [0, 1, 7, 729, 2024, 117440, u64::MAX] {
+                let mut computer = Computer::<u64>::new(program.to_vec(), a, 5, 9);
+                let expected = computer.run();
+
+                let mut outputs = Vec::new();
+                assert_eq!(compiled.run([a, 5, 9], &mut outputs), expected, "{:?} with A = {}", program, a);
+                assert_eq!(outputs, computer.outputs, "{:?} with A = {}", program, a);
+            }
+        }
+    }
 }
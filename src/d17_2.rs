@@ -0,0 +1,141 @@
+use crate::d17_1::{parse_input, Compiled, Computer};
+use crate::{Answer, AocError, Solution};
+
+// The search only works for programs shaped like the puzzle's: a single loop
+// that ends in `jnz 0`, shifts A right by three bits with `adv 3` and outputs
+// one value per iteration. Each output then only depends on the bits of A
+// that are still left, so A can be rebuilt three bits at a time.
+fn check_loop_shape(program: &[i64]) -> Result<(), AocError> {
+    let instructions: Vec<(i64, i64)> = program.chunks_exact(2).map(|pair| (pair[0], pair[1])).collect();
+    let count = |opcode: i64| instructions.iter().filter(|&&(op, _)| op == opcode).count();
+
+    if !program.len().is_multiple_of(2) || instructions.last() != Some(&(3, 0)) || count(3) != 1 {
+        return Err(AocError::InvalidInput(
+            "program does not end in a single `jnz 0` loop".to_string(),
+        ));
+    }
+    if !instructions.contains(&(0, 3)) || count(0) != 1 {
+        return Err(AocError::InvalidInput(
+            "program does not shift A by 3 bits once per loop".to_string(),
+        ));
+    }
+    if count(5) != 1 {
+        return Err(AocError::InvalidInput(
+            "program does not output exactly once per loop".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Extends `prefix` (the high bits of A found so far) by one octal digit at a
+// time, working backwards from the last output. Digits are tried smallest
+// first, so the first complete match is the smallest A.
+fn search(
+    template: &Computer,
+    compiled: &Compiled,
+    prefix: u64,
+    remaining: usize,
+    outputs: &mut Vec<i64>,
+) -> Result<Option<u64>, AocError> {
+    if remaining == 0 {
+        return Ok(Some(prefix));
+    }
+    let expected = &template.program[remaining - 1..];
+
+    // Past 21 outputs A can outgrow the VM's 64-bit registers
+    let shifted = prefix.checked_mul(8).ok_or_else(|| {
+        AocError::InvalidInput(format!("program of {} values needs A wider than 64 bits", template.program.len()))
+    })?;
+    for digit in 0..8 {
+        let a = shifted | digit;
+        if a == 0 {
+            continue;
+        }
+
+        outputs.clear();
+        compiled.run([a, template.register_b, template.register_c], outputs)?;
+        if outputs == expected {
+            if let Some(a) = search(template, compiled, a, remaining - 1, outputs)? {
+                return Ok(Some(a));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn find_quine(computer: &Computer) -> Result<u64, AocError> {
+    check_loop_shape(&computer.program)?;
+    // Every candidate runs the whole program, so decode it only once
+    let compiled = Compiled::new(&computer.program);
+    search(computer, &compiled, 0, computer.program.len(), &mut Vec::new())?.ok_or(AocError::NoSolution)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let computer = parse_input(input)?;
+        Ok(find_quine(&computer)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input::<u64>(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let input = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+        let computer = parse_input(input).unwrap();
+        assert_eq!(find_quine(&computer), Ok(117440));
+
+        let mut check = Computer::<u64>::new(computer.program.clone(), 117440, 0, 0);
+        check.run().unwrap();
+        assert_eq!(check.outputs, computer.program);
+    }
+
+    #[test]
+    fn test_unsupported_programs() {
+        // Shifts A by one bit per loop
+        let computer = Computer::new(vec![0, 1, 5, 4, 3, 0], 729, 0, 0);
+        assert_eq!(
+            find_quine(&computer),
+            Err(AocError::InvalidInput("program does not shift A by 3 bits once per loop".to_string()))
+        );
+
+        // No loop at all
+        let computer = Computer::new(vec![0, 3, 5, 4], 0, 0, 0);
+        assert!(find_quine(&computer).is_err());
+
+        // The right shape, but the output can never match the program
+        let computer = Computer::new(vec![0, 3, 5, 0, 3, 0], 0, 0, 0);
+        assert_eq!(find_quine(&computer), Err(AocError::NoSolution));
+
+        // Like the example padded with `bxl 0`s: it prints A's octal digits
+        // past the lowest, so a 24 value quine needs a 75 bit A
+        let mut program = vec![0, 3];
+        program.extend([1, 0].repeat(9));
+        program.extend([5, 4, 3, 0]);
+        let computer = Computer::new(program, 0, 0, 0);
+        assert_eq!(
+            find_quine(&computer),
+            Err(AocError::InvalidInput("program of 24 values needs A wider than 64 bits".to_string()))
+        );
+    }
+}
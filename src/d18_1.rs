@@ -1,103 +1,117 @@
-use std::collections::{BinaryHeap, HashSet};
-use std::cmp::Ordering;
-
-#[derive(Copy, Clone, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: (i32, i32),
+use crate::error::non_empty_lines;
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::search::{bfs, Path};
+use crate::{Answer, AocError, Solution};
+
+/// Side of the puzzle's memory space; the example uses 7.
+pub(crate) const GRID_SIZE: usize = 71;
+/// How many bytes have fallen when part 1 asks for the path; the example uses 12.
+const FALLEN: usize = 1024;
+
+pub(crate) fn parse_input(input: &str) -> Result<Vec<Point>, AocError> {
+    non_empty_lines(input)
+        .map(|line| {
+            let (x, y) = line.split_once(",")?;
+            Ok(Point::new(line.parse(x)?, line.parse(y)?))
+        })
+        .collect()
 }
 
-// Custom ordering for our priority queue
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
+// A `grid_size` square memory space with `bytes` marked as corrupted.
+pub(crate) fn memory_grid(bytes: &[Point], grid_size: usize) -> Grid<bool> {
+    let mut grid = Grid::new(grid_size, grid_size, false);
+    for &byte in bytes {
+        grid.set(byte, true);
     }
+    grid
 }
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+pub(crate) fn find_shortest_path(corrupted: &Grid<bool>) -> Option<usize> {
+    let start = Point::new(0, 0);
+    let goal = Point::new(corrupted.width() as i64 - 1, corrupted.height() as i64 - 1);
+
+    // Every step costs the same, so a plain BFS finds the shortest path
+    let path = bfs(
+        start,
+        |&position| corrupted.neighbors4(position).filter(|&next| !corrupted[next]),
+        |&position| position == goal,
+    )?;
+    Some(path.cost as usize)
 }
 
-fn parse_input(input: &str) -> Vec<(i32, i32)> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let mut parts = line.trim().split(',');
-            let x = parts.next().unwrap().parse().unwrap();
-            let y = parts.next().unwrap().parse().unwrap();
-            (x, y)
-        })
-        .collect()
-}
+/// Shortest walk to the exit when byte `i` lands at time step `i` instead of
+/// all of them being there from the start. Each step takes one time unit and
+/// a cell can be stood on only before its byte lands. The path's cost is the
+/// arrival time, and the walker is at `nodes[t]` at time `t`.
+pub(crate) fn race_corruption(bytes: &[Point], grid_size: usize) -> Result<Option<Path<Point>>, AocError> {
+    if grid_size == 0 {
+        return Err(AocError::InvalidInput("the memory space has no cells".to_string()));
+    }
+    let mut lands_at = Grid::new(grid_size, grid_size, usize::MAX);
+    for (time, &byte) in bytes.iter().enumerate().rev() {
+        lands_at.set(byte, time);
+    }
+    let open = |position: Point, time: usize| time < lands_at[position];
 
-fn find_shortest_path(corrupted: &HashSet<(i32, i32)>, grid_size: i32) -> Option<usize> {
-    let start = (0, 0);
-    let goal = (grid_size - 1, grid_size - 1);
-    
-    let mut distances = HashSet::new();
-    let mut heap = BinaryHeap::new();
+    let start = Point::new(0, 0);
+    let goal = Point::new(grid_size as i64 - 1, grid_size as i64 - 1);
+    if !open(start, 0) {
+        return Ok(None);
+    }
 
-    // Start from the beginning with cost 0
-    heap.push(State { cost: 0, position: start });
-    distances.insert((0, start));
+    // Cells only ever close, so waiting never helps and reaching a cell
+    // earlier beats reaching it later: each cell is entered at most once.
+    let mut entered = Grid::new(grid_size, grid_size, false);
+    entered.set(start, true);
+    let Some(path) = bfs(
+        (start, 0),
+        |&(position, time)| {
+            let next: Vec<(Point, usize)> = lands_at
+                .neighbors4(position)
+                .filter(|&next| open(next, time + 1) && !entered[next])
+                .map(|next| (next, time + 1))
+                .collect();
+            for &(cell, _) in &next {
+                entered.set(cell, true);
+            }
+            next
+        },
+        |&(position, _)| position == goal,
+    ) else {
+        return Ok(None);
+    };
+    Ok(Some(Path {
+        cost: path.cost,
+        nodes: path.nodes.into_iter().map(|(position, _)| position).collect(),
+    }))
+}
 
-    // Possible moves: up, right, down, left
-    let directions = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+// Steps to the exit of a `grid_size` memory space once the first `fallen`
+// bytes have landed.
+fn steps_after(bytes: &[Point], grid_size: usize, fallen: usize) -> Option<usize> {
+    find_shortest_path(&memory_grid(&bytes[..bytes.len().min(fallen)], grid_size))
+}
 
-    while let Some(State { cost, position }) = heap.pop() {
-        // If we've reached the goal, return the cost
-        if position == goal {
-            return Some(cost);
-        }
+pub struct Solver;
 
-        // Look at all possible moves
-        for &(dx, dy) in &directions {
-            let new_pos = (position.0 + dx, position.1 + dy);
-            
-            // Check if the new position is valid
-            if new_pos.0 >= 0 && new_pos.0 < grid_size &&
-               new_pos.1 >= 0 && new_pos.1 < grid_size &&
-               !corrupted.contains(&new_pos) {
-                
-                let new_state = State {
-                    cost: cost + 1,
-                    position: new_pos,
-                };
-
-                // If we haven't seen this position before or found a better path
-                if distances.insert((new_state.cost, new_state.position)) {
-                    heap.push(new_state);
-                }
-            }
-        }
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        18
     }
 
-    None // No path found
-}
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let byte_positions = parse_input(input)?;
+        let steps = steps_after(&byte_positions, GRID_SIZE, FALLEN).ok_or(AocError::NoSolution)?;
+        Ok(steps.into())
+    }
 
-#[test]
-fn main() {
-    let input = include_str!("d18.in");
-
-    let byte_positions = parse_input(input);
-    
-    // For the first part, we only need to consider the first 1024 bytes
-    let corrupted: HashSet<(i32, i32)> = byte_positions.iter()
-        .take(1024)
-        .copied()
-        .collect();
-
-    // Use 71 for the actual puzzle, 7 for the example
-    let grid_size = 71;
-    
-    if let Some(steps) = find_shortest_path(&corrupted, grid_size) {
-        println!("Minimum steps needed: {}", steps);
-    } else {
-        println!("No path found!");
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
     }
 }
 
@@ -119,10 +133,34 @@ mod tests {
 3,3
 2,6
 5,1";
-        
-        let byte_positions = parse_input(input);
-        let corrupted: HashSet<(i32, i32)> = byte_positions.iter().copied().collect();
-        
-        assert_eq!(find_shortest_path(&corrupted, 7), Some(22));
+
+        let byte_positions = parse_input(input).unwrap();
+        assert_eq!(steps_after(&byte_positions, 7, 12), Some(22));
+        assert_eq!(steps_after(&byte_positions, 7, 0), Some(12));
+
+        // Falling one per step, the bytes never catch up with the walker
+        let path = race_corruption(&byte_positions, 7).unwrap().unwrap();
+        assert_eq!(path.cost, 12);
+        for (time, &position) in path.nodes.iter().enumerate() {
+            assert!(!byte_positions[..byte_positions.len().min(time + 1)].contains(&position));
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_race_corruption() {
+        let points = |coords: &[(i64, i64)]| coords.iter().map(|&(x, y)| Point::new(x, y)).collect::<Vec<_>>();
+
+        // (0, 1) closes as the walker would step onto it, but (1, 0) is still
+        // open then, even though a static map has no way through
+        let bytes = points(&[(1, 1), (0, 1), (1, 0)]);
+        assert_eq!(steps_after(&bytes, 3, bytes.len()), None);
+        let path = race_corruption(&bytes, 3).unwrap().unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.nodes, points(&[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]));
+
+        // Both ways out of the corner close before the walker can use them
+        assert_eq!(race_corruption(&points(&[(1, 0), (0, 1)]), 3), Ok(None));
+        assert_eq!(race_corruption(&points(&[(0, 0)]), 3), Ok(None));
+        assert!(race_corruption(&[], 0).is_err());
+    }
+}
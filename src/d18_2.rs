@@ -0,0 +1,183 @@
+use crate::d18_1::{find_shortest_path, memory_grid, parse_input, GRID_SIZE};
+use crate::geometry::Point;
+use crate::grid::ORTHOGONAL;
+use crate::{Answer, AocError, Solution};
+
+// Disjoint sets over the cells of the memory space, with path halving and
+// union by size.
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        UnionFind {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+    }
+}
+
+// Lets every byte fall, then lifts them again in reverse order, joining each
+// freed cell to its free neighbours. The byte whose removal first connects
+// the start to the exit is the one that cut them apart.
+fn first_blocking_byte(bytes: &[Point], grid_size: usize) -> Result<Option<Point>, AocError> {
+    if grid_size == 0 {
+        return Err(AocError::InvalidInput("the memory space has no cells".to_string()));
+    }
+    let index = |p: Point| {
+        (p.x >= 0 && p.y >= 0 && (p.x as usize) < grid_size && (p.y as usize) < grid_size)
+            .then(|| p.y as usize * grid_size + p.x as usize)
+    };
+    // A byte may fall on the same cell twice, so count them
+    let mut corrupted = vec![0usize; grid_size * grid_size];
+    for &byte in bytes {
+        if let Some(i) = index(byte) {
+            corrupted[i] += 1;
+        }
+    }
+
+    let mut sets = UnionFind::new(grid_size * grid_size);
+    let free = |corrupted: &[usize], p: Point| index(p).filter(|&i| corrupted[i] == 0);
+    // Joins cell `i`, at `p`, to its free neighbours
+    let free_cell = |sets: &mut UnionFind, corrupted: &[usize], i: usize, p: Point| {
+        for delta in ORTHOGONAL {
+            if let Some(j) = free(corrupted, p + delta) {
+                sets.union(i, j);
+            }
+        }
+    };
+
+    for y in 0..grid_size as i64 {
+        for x in 0..grid_size as i64 {
+            let p = Point::new(x, y);
+            if let Some(i) = free(&corrupted, p) {
+                free_cell(&mut sets, &corrupted, i, p);
+            }
+        }
+    }
+
+    let (start, exit) = (0, grid_size * grid_size - 1);
+    let connected = |sets: &mut UnionFind, corrupted: &[usize]| {
+        corrupted[start] == 0 && corrupted[exit] == 0 && sets.find(start) == sets.find(exit)
+    };
+    if connected(&mut sets, &corrupted) {
+        return Ok(None);
+    }
+
+    for &byte in bytes.iter().rev() {
+        let Some(i) = index(byte) else {
+            continue;
+        };
+        corrupted[i] -= 1;
+        if corrupted[i] == 0 {
+            free_cell(&mut sets, &corrupted, i, byte);
+        }
+        if connected(&mut sets, &corrupted) {
+            return Ok(Some(byte));
+        }
+    }
+    Ok(None)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        18
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let byte_positions = parse_input(input)?;
+        let byte = first_blocking_byte(&byte_positions, GRID_SIZE)?.ok_or(AocError::NoSolution)?;
+        Ok(format!("{},{}", byte.x, byte.y).into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+    #[test]
+    fn test_example() {
+        let bytes = parse_input(EXAMPLE).unwrap();
+        assert_eq!(first_blocking_byte(&bytes, 7), Ok(Some(Point::new(6, 1))));
+    }
+
+    #[test]
+    fn test_matches_path_search() {
+        // The first prefix with no path ends in the blocking byte
+        let bytes = parse_input(EXAMPLE).unwrap();
+        let blocked = (1..=bytes.len())
+            .find(|&n| find_shortest_path(&memory_grid(&bytes[..n], 7)).is_none())
+            .map(|n| bytes[n - 1]);
+        assert_eq!(Ok(blocked), first_blocking_byte(&bytes, 7));
+
+        // Only a few bytes: the exit is never cut off
+        assert_eq!(first_blocking_byte(&bytes[..12], 7), Ok(None));
+
+        // A byte landing on the exit blocks it straight away
+        assert_eq!(first_blocking_byte(&[Point::new(0, 1), Point::new(2, 2)], 3), Ok(Some(Point::new(2, 2))));
+
+        // Bytes outside the memory space never block anything
+        assert_eq!(first_blocking_byte(&[Point::new(-1, 5), Point::new(9, 1)], 3), Ok(None));
+
+        // No memory space at all
+        assert!(first_blocking_byte(&bytes, 0).is_err());
+    }
+}
@@ -1,5 +1,5 @@
+use crate::{Answer, AocError, Solution};
 use std::collections::HashSet;
-use std::fs::read_to_string;
 
 fn can_make_pattern(
     target: &str,
@@ -31,34 +31,44 @@ fn can_make_pattern(
     memo.insert(target.to_string());
     false
 }
-#[test]
-fn main() {
-    // Read input file
-    let input = read_to_string("src/d19.in").expect("Failed to read input file");
-    let mut lines = input.lines();
-
-    // Parse patterns from first line
-    let patterns: HashSet<String> = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|s| s.to_string())
-        .collect();
 
-    // Skip empty line
-    lines.next();
+pub struct Solver;
 
-    // Process each design
-    let mut possible_count = 0;
-    let mut memo = HashSet::new();
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        19
+    }
 
-    for design in lines {
-        if can_make_pattern(design, &patterns, &mut memo) {
-            possible_count += 1;
-        }
+    fn part(&self) -> u32 {
+        1
     }
 
-    println!("Number of possible designs: {}", possible_count);
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut lines = input.lines();
+
+        // Parse patterns from first line
+        let patterns: HashSet<String> = lines
+            .next()
+            .ok_or_else(|| AocError::InvalidInput("missing towel patterns".to_string()))?
+            .split(", ")
+            .map(|s| s.to_string())
+            .collect();
+
+        // Skip empty line
+        lines.next();
+
+        // Process each design
+        let mut possible_count = 0;
+        let mut memo = HashSet::new();
+
+        for design in lines {
+            if can_make_pattern(design, &patterns, &mut memo) {
+                possible_count += 1;
+            }
+        }
+
+        Ok(possible_count.into())
+    }
 }
 
 #[cfg(test)]
@@ -95,4 +105,4 @@ mod tests {
             );
         }
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
-use std::fs::read_to_string;
 
 fn count_arrangements(
     target: &str,
@@ -30,38 +30,42 @@ fn count_arrangements(
     memo.insert(target.to_string(), total);
     total
 }
-#[test]
-fn main() {
-    // Read input file
-    let input = read_to_string("src/d19.in").expect("Failed to read input file");
-    let mut lines = input.lines();
-
-    // Parse patterns from first line
-    let patterns: Vec<String> = lines
-        .next()
-        .unwrap()
-        .split(", ")
-        .map(|s| s.to_string())
-        .collect();
 
-    // Skip empty line
-    lines.next();
+pub struct Solver;
 
-    // Process each design
-    let mut total_arrangements = 0;
-    let mut memo = HashMap::new();
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        19
+    }
 
-    for design in lines {
-        let arrangements = count_arrangements(design, &patterns, &mut memo);
-        if arrangements > 0 {
-            println!("{}: {} ways", design, arrangements);
-            total_arrangements += arrangements;
-        } else {
-            println!("{}: impossible", design);
-        }
+    fn part(&self) -> u32 {
+        2
     }
 
-    println!("\nTotal number of possible arrangements: {}", total_arrangements);
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut lines = input.lines();
+
+        // Parse patterns from first line
+        let patterns: Vec<String> = lines
+            .next()
+            .ok_or_else(|| AocError::InvalidInput("missing towel patterns".to_string()))?
+            .split(", ")
+            .map(|s| s.to_string())
+            .collect();
+
+        // Skip empty line
+        lines.next();
+
+        // Process each design
+        let mut total_arrangements = 0;
+        let mut memo = HashMap::new();
+
+        for design in lines {
+            total_arrangements += count_arrangements(design, &patterns, &mut memo);
+        }
+
+        Ok(total_arrangements.into())
+    }
 }
 
 #[cfg(test)]
@@ -98,4 +102,4 @@ mod tests {
             );
         }
     }
-}
\ No newline at end of file
+}
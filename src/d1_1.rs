@@ -1,43 +1,53 @@
-use std::io::{self, BufRead};
-#[test]
-fn main() -> io::Result<()> {
-    // Read input from stdin
-    let stdin = io::stdin();
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
+fn parse_lists(input: &str) -> Result<(Vec<i32>, Vec<i32>), AocError> {
     let mut left_list = Vec::new();
     let mut right_list = Vec::new();
 
-    // Parse input
-    for line in stdin.lock().lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let numbers: Vec<i32> = line
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        if numbers.len() >= 2 {
-            left_list.push(numbers[0]);
-            right_list.push(numbers[1]);
-        }
+    // Each line holds one number from each list
+    for line in non_empty_lines(input) {
+        let mut numbers = line.text.split_whitespace();
+        let (Some(left), Some(right), None) = (numbers.next(), numbers.next(), numbers.next()) else {
+            return Err(line.error("expected two numbers"));
+        };
+        left_list.push(line.parse(left)?);
+        right_list.push(line.parse(right)?);
     }
 
-    // Sort both lists independently
-    left_list.sort_unstable();
-    right_list.sort_unstable();
+    Ok((left_list, right_list))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let (mut left_list, mut right_list) = parse_lists(input)?;
+
+        // Sort both lists independently
+        left_list.sort_unstable();
+        right_list.sort_unstable();
 
-    // Calculate total distance
-    let total_distance: i64 = left_list
-        .iter()
-        .zip(right_list.iter())
-        .map(|(a, b)| (*a as i64 - *b as i64).abs())
-        .sum();
+        // Calculate total distance
+        let total_distance: i64 = left_list
+            .iter()
+            .zip(right_list.iter())
+            .map(|(a, b)| (*a as i64 - *b as i64).abs())
+            .sum();
 
-    println!("Total distance: {}", total_distance);
+        Ok(total_distance.into())
+    }
 
-    Ok(())
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_lists(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -46,8 +56,8 @@ mod tests {
 
     #[test]
     fn test_example_case() {
-        let mut left = vec![3, 4, 2, 1, 3, 3];
-        let mut right = vec![4, 3, 5, 3, 9, 3];
+        let mut left = [3, 4, 2, 1, 3, 3];
+        let mut right = [4, 3, 5, 3, 9, 3];
 
         left.sort_unstable();
         right.sort_unstable();
@@ -60,4 +70,10 @@ mod tests {
 
         assert_eq!(total, 11);
     }
+
+    #[test]
+    fn test_solver() {
+        let answer = Solver.solve("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        assert_eq!(answer, Answer::Int(11));
+    }
 }
@@ -1,45 +1,56 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
-use std::io::{self, BufRead};
-#[test]
-fn main() -> io::Result<()> {
-    // Read input from stdin
-    let stdin = io::stdin();
+
+fn parse_lists(input: &str) -> Result<(Vec<i64>, Vec<i64>), AocError> {
     let mut left_list = Vec::new();
     let mut right_list = Vec::new();
 
-    // Parse input
-    for line in stdin.lock().lines() {
-        let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
+    // Each line holds one number from each list
+    for line in non_empty_lines(input) {
+        let mut numbers = line.text.split_whitespace();
+        let (Some(left), Some(right), None) = (numbers.next(), numbers.next(), numbers.next()) else {
+            return Err(line.error("expected two numbers"));
+        };
+        left_list.push(line.parse(left)?);
+        right_list.push(line.parse(right)?);
+    }
+
+    Ok((left_list, right_list))
+}
 
-        let numbers: Vec<i64> = line
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
+pub struct Solver;
 
-        if numbers.len() >= 2 {
-            left_list.push(numbers[0]);
-            right_list.push(numbers[1]);
-        }
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        1
     }
 
-    // Count occurrences in right list
-    let mut right_counts: HashMap<i64, i64> = HashMap::new();
-    for &num in &right_list {
-        *right_counts.entry(num).or_insert(0) += 1;
+    fn part(&self) -> u32 {
+        2
     }
 
-    // Calculate similarity score
-    let similarity_score: i64 = left_list
-        .iter()
-        .map(|&num| num * right_counts.get(&num).unwrap_or(&0))
-        .sum();
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let (left_list, right_list) = parse_lists(input)?;
+
+        // Count occurrences in right list
+        let mut right_counts: HashMap<i64, i64> = HashMap::new();
+        for &num in &right_list {
+            *right_counts.entry(num).or_insert(0) += 1;
+        }
+
+        // Calculate similarity score
+        let similarity_score: i64 = left_list
+            .iter()
+            .map(|&num| num * right_counts.get(&num).unwrap_or(&0))
+            .sum();
 
-    println!("Similarity score: {}", similarity_score);
+        Ok(similarity_score.into())
+    }
 
-    Ok(())
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_lists(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -48,7 +59,7 @@ mod tests {
 
     #[test]
     fn test_example_case() {
-        let left = vec![3, 4, 2, 1, 3, 3];
+        let left = [3, 4, 2, 1, 3, 3];
         let right = vec![4, 3, 5, 3, 9, 3];
 
         let mut right_counts: HashMap<i64, i64> = HashMap::new();
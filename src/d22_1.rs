@@ -1,3 +1,5 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 fn mix(secret: u64, value: u64) -> u64 {
     secret ^ value
 }
@@ -32,26 +34,30 @@ fn generate_nth_secret(initial: u64, n: usize) -> u64 {
     }
     secret
 }
-#[test]
-fn main() {
-    // Example input
-    let inputs = include_str!("d22.in")
-        .lines()
-        .map(|line| line.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-
-    // Generate 2000th secret for each input and sum them
-    let sum: u64 = inputs
-        .iter()
-        .map(|&initial| generate_nth_secret(initial, 2000))
-        .sum();
-
-    println!("Sum of 2000th secrets: {}", sum);
-
-    // Print individual 2000th secrets for verification
-    for &initial in &inputs {
-        let secret_2000 = generate_nth_secret(initial, 2000);
-        //   println!("{}: {}", initial, secret_2000);
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        22
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let inputs = non_empty_lines(input)
+            .map(|line| line.parse::<u64>(line.text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Generate 2000th secret for each input and sum them
+        let sum: u64 = inputs
+            .iter()
+            .map(|&initial| generate_nth_secret(initial, 2000))
+            .sum();
+
+        Ok(sum.into())
     }
 }
 
@@ -1,3 +1,5 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashMap;
 
 fn calculate_next_secret(secret: u64) -> u64 {
@@ -83,32 +85,25 @@ fn find_best_sequence(initial_secrets: &[u64]) -> i64 {
         }
     }
 
-    println!("Best sequence: {:?}", best_sequence);
     best_total_bananas
 }
-#[test]
 
-fn main() {
-    let initial_secrets_part1: Vec<u64> = vec![1, 10, 100, 2024];
-    let initial_secrets_part2: Vec<u64> = vec![1, 2, 3, 2024];
+pub struct Solver;
 
-    // Part 1
-    let mut sum_of_2000th_secrets: u64 = 0;
-    for &secret in &initial_secrets_part1 {
-        let secrets = generate_secrets(secret, 2000);
-        sum_of_2000th_secrets += secrets[2000];
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        22
     }
-    println!("Part 1: Sum of 2000th secrets: {}", sum_of_2000th_secrets);
 
-    // Part 2
-    let max_bananas = find_best_sequence(&initial_secrets_part2);
-    println!("Part 2: Max bananas: {}", max_bananas);
+    fn part(&self) -> u32 {
+        2
+    }
 
-    let initial_secrets_part2_final: Vec<u64> = include_str!("d22.in")
-        .lines()
-        .map(|line| line.parse().unwrap())
-        .collect();
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let initial_secrets: Vec<u64> = non_empty_lines(input)
+            .map(|line| line.parse(line.text))
+            .collect::<Result<_, _>>()?;
 
-    let max_bananas_final = find_best_sequence(&initial_secrets_part2_final);
-    println!("Part 2: Max bananas final: {}", max_bananas_final);
+        Ok(find_best_sequence(&initial_secrets).into())
+    }
 }
@@ -1,19 +1,29 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet};
-#[test]
-fn main() {
-    let input = include_str!("d23.in");
+pub struct Solver;
 
-    let result = find_triplets(input);
-    println!("Total triplets with 't' computer: {}", result);
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(find_triplets(input)?.into())
+    }
 }
 
-fn find_triplets(input: &str) -> usize {
+fn find_triplets(input: &str) -> Result<usize, AocError> {
     // Create adjacency list representation
     let mut connections: HashMap<&str, HashSet<&str>> = HashMap::new();
 
     // Parse input and build connections
-    for line in input.lines() {
-        let (a, b) = line.split_once('-').unwrap();
+    for line in non_empty_lines(input) {
+        let (a, b) = line.split_once("-")?;
         connections.entry(a).or_default().insert(b);
         connections.entry(b).or_default().insert(a);
     }
@@ -38,7 +48,7 @@ fn find_triplets(input: &str) -> usize {
                         // Check if computer3 is connected to computer1
                         if connections[computer1].contains(computer3) {
                             // Create sorted triplet to avoid duplicates
-                            let mut triplet = vec![computer1, computer2, computer3];
+                            let mut triplet = [computer1, computer2, computer3];
                             triplet.sort();
 
                             triplets.insert((triplet[0], triplet[1], triplet[2]));
@@ -50,10 +60,10 @@ fn find_triplets(input: &str) -> usize {
     }
 
     // Count triplets containing at least one computer starting with 't'
-    triplets
+    Ok(triplets
         .iter()
         .filter(|&&(a, b, c)| a.starts_with('t') || b.starts_with('t') || c.starts_with('t'))
-        .count()
+        .count())
 }
 
 #[cfg(test)]
@@ -67,6 +77,6 @@ qp-kh
 de-cg
 ka-co
 yn-aq";
-        assert_eq!(find_triplets(input), 0);
+        assert_eq!(find_triplets(input), Ok(0));
     }
 }
@@ -1,19 +1,29 @@
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet};
-#[test]
-fn main() {
-    let input = include_str!("d23.in");
+pub struct Solver;
 
-    let password = find_lan_party_password(input);
-    println!("LAN party password: {}", password);
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        23
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(find_lan_party_password(input)?.into())
+    }
 }
 
-fn find_lan_party_password(input: &str) -> String {
+fn find_lan_party_password(input: &str) -> Result<String, AocError> {
     // Create adjacency list representation
     let mut connections: HashMap<String, HashSet<String>> = HashMap::new();
 
     // Parse input and build connections
-    for line in input.lines() {
-        let (a, b) = line.split_once('-').unwrap();
+    for line in non_empty_lines(input) {
+        let (a, b) = line.split_once("-")?;
         connections
             .entry(a.to_string())
             .or_default()
@@ -33,7 +43,7 @@ fn find_lan_party_password(input: &str) -> String {
     // Sort computers alphabetically and join with commas
     let mut password: Vec<String> = max_clique.into_iter().collect();
     password.sort();
-    password.join(",")
+    Ok(password.join(","))
 }
 
 fn find_maximum_clique(
@@ -83,8 +93,8 @@ fn expand_clique(
             // Create new candidates list with only computers connected to everything
             let mut new_candidates: Vec<String> = candidates
                 .iter()
+                .filter(|&c| current.iter().all(|curr| connections[curr].contains(c)))
                 .cloned()
-                .filter(|c| current.iter().all(|curr| connections[curr].contains(c)))
                 .collect();
 
             // Recursively expand clique
@@ -103,6 +113,6 @@ mod tests {
     #[test]
     fn test_find_lan_party_password() {
         let input = "ka-co\nta-co\nde-co\nta-ka\nde-ta\nka-de";
-        assert_eq!(find_lan_party_password(input), "co,de,ka,ta");
+        assert_eq!(find_lan_party_password(input).as_deref(), Ok("co,de,ka,ta"));
     }
 }
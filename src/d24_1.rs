@@ -1,8 +1,11 @@
-use std::collections::{HashMap, HashSet};
+use crate::error::sections;
+use crate::{Answer, AocError, Solution};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Gate {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Gate {
     And,
     Or,
     Xor,
@@ -16,15 +19,26 @@ impl FromStr for Gate {
             "AND" => Ok(Gate::And),
             "OR" => Ok(Gate::Or),
             "XOR" => Ok(Gate::Xor),
-            _ => Err(format!("Unknown gate type: {}", s)),
+            _ => Err(format!("unknown gate type {:?}", s)),
         }
     }
 }
 
-#[derive(Debug)]
-struct Circuit {
-    wires: HashMap<String, Option<bool>>,
-    gates: Vec<(String, String, Gate, String)>, // (input1, input2, gate_type, output)
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Gate::And => "AND",
+            Gate::Or => "OR",
+            Gate::Xor => "XOR",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Circuit {
+    pub(crate) wires: HashMap<String, Option<bool>>,
+    pub(crate) gates: Vec<(String, String, Gate, String)>, // (input1, input2, gate_type, output)
 }
 
 impl Circuit {
@@ -53,89 +67,379 @@ impl Circuit {
         self.wires.entry(input2.to_string()).or_insert(None);
     }
 
-    fn evaluate_gate(&self, input1: bool, input2: bool, gate: Gate) -> bool {
-        match gate {
-            Gate::And => input1 && input2,
-            Gate::Or => input1 || input2,
-            Gate::Xor => input1 ^ input2,
+    /// A copy of the circuit with the output wires of each pair of gates
+    /// swapped, e.g. to try out a proposed fix.
+    pub(crate) fn with_swaps(&self, swaps: &[(&str, &str)]) -> Result<Circuit, AocError> {
+        let mut circuit = self.clone();
+        for &(a, b) in swaps {
+            let driver = |wire: &str| {
+                circuit
+                    .gates
+                    .iter()
+                    .position(|(_, _, _, output)| output == wire)
+                    .ok_or_else(|| AocError::InvalidInput(format!("no gate drives wire {}", wire)))
+            };
+            let (i, j) = (driver(a)?, driver(b)?);
+            circuit.gates[i].3 = b.to_string();
+            circuit.gates[j].3 = a.to_string();
         }
+        Ok(circuit)
     }
 
-    fn simulate(&mut self) {
-        let mut changed = true;
-        let mut evaluated = HashSet::new();
+    /// Orders the gates so every gate comes after the gates driving its
+    /// inputs. Fails if a wire has no value and no gate driving it, has two
+    /// gates driving it, or depends on itself.
+    pub(crate) fn schedule(&self) -> Result<Schedule, AocError> {
+        let mut names: Vec<&String> = self.wires.keys().collect();
+        names.sort();
+        let index: HashMap<String, usize> = names.iter().enumerate().map(|(i, &name)| (name.clone(), i)).collect();
+
+        let mut driver = vec![None; names.len()];
+        for (i, (_, _, _, output)) in self.gates.iter().enumerate() {
+            if driver[index[output]].replace(i).is_some() {
+                return Err(AocError::InvalidInput(format!("wire {} is driven by more than one gate", output)));
+            }
+        }
+        for (i, name) in names.iter().enumerate() {
+            if driver[i].is_none() && self.wires[*name].is_none() {
+                return Err(AocError::InvalidInput(format!("wire {} is never driven", name)));
+            }
+        }
 
-        while changed {
-            changed = false;
-            for (input1, input2, gate, output) in self.gates.clone() {
-                if evaluated.contains(&output) {
-                    continue;
+        // Kahn's algorithm: a gate is ready once every gate driving it is done
+        let inputs = |gate: usize| {
+            let (a, b, _, _) = &self.gates[gate];
+            [index[a], index[b]]
+        };
+        let mut waiting: Vec<usize> = (0..self.gates.len())
+            .map(|gate| inputs(gate).iter().filter(|&&wire| driver[wire].is_some()).count())
+            .collect();
+        let mut consumers = vec![Vec::new(); names.len()];
+        for gate in 0..self.gates.len() {
+            for wire in inputs(gate) {
+                consumers[wire].push(gate);
+            }
+        }
+
+        let mut ready: Vec<usize> = (0..self.gates.len()).filter(|&gate| waiting[gate] == 0).collect();
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(gate) = ready.pop() {
+            order.push(gate);
+            for &next in &consumers[index[&self.gates[gate].3]] {
+                waiting[next] -= 1;
+                if waiting[next] == 0 {
+                    ready.push(next);
                 }
+            }
+        }
 
-                if let (Some(val1), Some(val2)) = (self.wires[&input1], self.wires[&input2]) {
-                    let result = self.evaluate_gate(val1, val2, gate);
-                    self.wires.insert(output.clone(), Some(result));
-                    evaluated.insert(output);
-                    changed = true;
+        if order.len() < self.gates.len() {
+            // Every gate left over waits on another left-over gate, so
+            // following those inputs backwards has to come round in a loop
+            let mut gate = (0..self.gates.len()).find(|&gate| waiting[gate] > 0).unwrap();
+            let mut seen = HashSet::new();
+            while seen.insert(gate) {
+                gate = inputs(gate)
+                    .into_iter()
+                    .filter_map(|wire| driver[wire])
+                    .find(|&prev| waiting[prev] > 0)
+                    .unwrap();
+            }
+            return Err(AocError::InvalidInput(format!(
+                "wire {} depends on its own value",
+                self.gates[gate].3
+            )));
+        }
+
+        Ok(Schedule {
+            values: names.iter().map(|&name| self.wires[name].unwrap_or(false)).collect(),
+            gates: order
+                .into_iter()
+                .map(|gate| {
+                    let (a, b, kind, output) = &self.gates[gate];
+                    (index[a], index[b], *kind, index[output])
+                })
+                .collect(),
+            driven: driver.iter().map(Option::is_some).collect(),
+            names: names.into_iter().cloned().collect(),
+            index,
+        })
+    }
+
+    /// Renders the gate network as Graphviz DOT. Gates are nodes labelled
+    /// with their type and wires are the edges between them. The `x` and `y`
+    /// inputs and the `z` output of each bit are boxed together. Gates
+    /// driving a wire in `highlight` are drawn in red.
+    pub(crate) fn to_dot(&self, highlight: &[String]) -> String {
+        let mut gates: Vec<_> = self.gates.iter().collect();
+        gates.sort_by(|l, r| l.3.cmp(&r.3));
+        let driven: HashSet<&str> = gates.iter().map(|(_, _, _, output)| output.as_str()).collect();
+        let mut inputs: Vec<&str> = self.wires.keys().map(String::as_str).filter(|w| !driven.contains(w)).collect();
+        inputs.sort();
+
+        let mut out = String::from("digraph circuit {\n    node [fontname=monospace];\n");
+        for &wire in &inputs {
+            out += &format!("    \"{}\" [shape=box];\n", wire);
+        }
+
+        for (a, b, gate, output) in &gates {
+            let style = if highlight.contains(output) { ", color=red, fontcolor=red" } else { "" };
+            out += &format!("    \"g_{}\" [label=\"{}\"{}];\n", output, gate, style);
+            for input in [a, b] {
+                if driven.contains(input.as_str()) {
+                    out += &format!("    \"g_{}\" -> \"g_{}\" [label=\"{}\"];\n", input, output, input);
+                } else {
+                    out += &format!("    \"{}\" -> \"g_{}\";\n", input, output);
                 }
             }
+            if matches!(bus_bit(output), Some(('z', _))) {
+                out += &format!("    \"{}\" [shape=box];\n", output);
+                out += &format!("    \"g_{}\" -> \"{}\";\n", output, output);
+            }
+        }
+        // Only real bus wires are grouped: undriven x and y, driven z
+        let mut bits: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+        let outputs = gates.iter().map(|(_, _, _, output)| output.as_str());
+        for wire in inputs.iter().copied().chain(outputs) {
+            match bus_bit(wire) {
+                Some(('x' | 'y', bit)) if !driven.contains(wire) => bits.entry(bit).or_default().push(wire),
+                Some(('z', bit)) if driven.contains(wire) => bits.entry(bit).or_default().push(wire),
+                _ => {}
+            }
         }
+
+        for (bit, wires) in bits {
+            let wires: Vec<String> = wires.iter().map(|w| format!("\"{}\"", w)).collect();
+            out += &format!(
+                "    subgraph cluster_{:02} {{ label=\"bit {:02}\"; {}; }}\n",
+                bit,
+                bit,
+                wires.join("; ")
+            );
+        }
+        out += "}\n";
+        out
     }
 
-    fn get_output(&self) -> u64 {
-        let mut z_wires: Vec<_> = self
-            .wires
-            .iter()
-            .filter(|(k, _)| k.starts_with('z'))
-            .collect();
+    pub(crate) fn simulate(&mut self) -> Result<(), AocError> {
+        let mut schedule = self.schedule()?;
+        schedule.evaluate();
+        for (name, value) in schedule.names.iter().zip(&schedule.values) {
+            self.wires.insert(name.clone(), Some(*value));
+        }
+        Ok(())
+    }
 
-        // Sort by the number after 'z' to get correct bit order
-        z_wires.sort_by_key(|(k, _)| k[1..].parse::<u32>().unwrap_or(0));
-        dbg!(&z_wires);
+    /// Reads bus `prefix` after [`Circuit::simulate`]. Fails if a bit is
+    /// missing or has no value.
+    pub(crate) fn read_bus(&self, prefix: &str) -> Result<Vec<bool>, AocError> {
+        bus_wires(self.wires.keys(), prefix)?
+            .into_iter()
+            .map(|wire| self.wires[wire].ok_or_else(|| AocError::InvalidInput(format!("wire {} has no value", wire))))
+            .collect()
+    }
 
-        let mut result = 0;
-        for (_, &value) in z_wires.iter().rev() {
-            result = (result << 1) | (value.unwrap_or(false) as u64);
+    /// Sets the initial values of input bus `prefix`, which must be exactly
+    /// as wide as `bits`.
+    pub(crate) fn set_bus(&mut self, prefix: &str, bits: &[bool]) -> Result<(), AocError> {
+        let wires: Vec<String> = bus_wires(self.wires.keys(), prefix)?.into_iter().map(String::from).collect();
+        check_width(prefix, wires.len(), bits.len())?;
+        for (wire, &bit) in wires.iter().zip(bits) {
+            if self.gates.iter().any(|(_, _, _, output)| output == wire) {
+                return Err(AocError::InvalidInput(format!("wire {} is driven by a gate", wire)));
+            }
+            self.wires.insert(wire.clone(), Some(bit));
         }
-        result
+        Ok(())
     }
 }
 
-fn parse_input(input: &str) -> Circuit {
-    let mut circuit = Circuit::new();
-    let mut parsing_initial = true;
+/// Splits an x, y or z bus wire such as `z07` into its bus and bit number.
+pub(crate) fn bus_bit(wire: &str) -> Option<(char, usize)> {
+    let mut chars = wire.chars();
+    let bus = chars.next().filter(|c| matches!(c, 'x' | 'y' | 'z'))?;
+    let digits = chars.as_str();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((bus, digits.parse().ok()?))
+}
 
-    for line in input.lines() {
-        let line = line.trim();
-        if line.is_empty() {
-            parsing_initial = false;
+/// The wires of bus `prefix`, least significant bit first. A bus is every
+/// wire named `prefix` followed by a bit number (`z00`, `z01`, ...), and the
+/// bits must run from 0 without gaps.
+fn bus_wires<'a>(names: impl IntoIterator<Item = &'a String>, prefix: &str) -> Result<Vec<&'a str>, AocError> {
+    let mut bits = BTreeMap::new();
+    for name in names {
+        let Some(suffix) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        if suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_digit()) {
             continue;
         }
+        let bit: usize = suffix
+            .parse()
+            .map_err(|_| AocError::InvalidInput(format!("bit number of wire {} is too large", name)))?;
+        if let Some(other) = bits.insert(bit, name.as_str()) {
+            return Err(AocError::InvalidInput(format!(
+                "wires {} and {} are both bit {} of bus {}",
+                other, name, bit, prefix
+            )));
+        }
+    }
 
-        if parsing_initial {
-            // Parse initial wire values
-            if let Some((wire, value)) = line.split_once(": ") {
-                circuit.add_initial_wire(wire, value == "1");
+    if bits.is_empty() {
+        return Err(AocError::InvalidInput(format!("no bus named {}", prefix)));
+    }
+    if let Some(missing) = (0..bits.len()).find(|bit| !bits.contains_key(bit)) {
+        return Err(AocError::InvalidInput(format!("bus {} is missing bit {}", prefix, missing)));
+    }
+    Ok(bits.into_values().collect())
+}
+
+fn check_width(prefix: &str, width: usize, bits: usize) -> Result<(), AocError> {
+    if width != bits {
+        return Err(AocError::InvalidInput(format!(
+            "bus {} is {} bits wide, got {} bits",
+            prefix, width, bits
+        )));
+    }
+    Ok(())
+}
+
+/// The lowest `width` bits of `n`, least significant first.
+pub(crate) fn to_bits(n: u64, width: usize) -> Vec<bool> {
+    (0..width).map(|bit| bit < 64 && n >> bit & 1 == 1).collect()
+}
+
+/// The number on a bus, if it fits in a `u64`.
+pub(crate) fn from_bits(bits: &[bool]) -> Option<u64> {
+    if bits.iter().skip(64).any(|&bit| bit) {
+        return None;
+    }
+    Some(bits.iter().take(64).enumerate().fold(0, |n, (bit, &value)| n | (value as u64) << bit))
+}
+
+/// A circuit compiled for evaluation: wires are numbered and the gates are
+/// in dependency order, so evaluating is a single pass that can be repeated
+/// cheaply after changing the inputs.
+#[derive(Debug, Clone)]
+pub(crate) struct Schedule {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    // (input1, input2, gate_type, output) by wire number
+    gates: Vec<(usize, usize, Gate, usize)>,
+    driven: Vec<bool>,
+    values: Vec<bool>,
+}
+
+impl Schedule {
+    /// Sets an input wire. Wires driven by a gate can't be set.
+    pub(crate) fn set(&mut self, wire: &str, value: bool) -> Result<(), AocError> {
+        match self.index.get(wire) {
+            Some(&i) if !self.driven[i] => {
+                self.values[i] = value;
+                Ok(())
             }
-        } else {
-            // Parse gate definitions
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() == 5 && parts[3] == "->" {
-                let input1 = parts[0];
-                let gate = Gate::from_str(parts[1]).unwrap();
-                let input2 = parts[2];
-                let output = parts[4];
-                circuit.add_gate(input1, input2, gate, output);
+            Some(_) => Err(AocError::InvalidInput(format!("wire {} is driven by a gate", wire))),
+            None => Err(AocError::InvalidInput(format!("no wire named {}", wire))),
+        }
+    }
+
+    pub(crate) fn get(&self, wire: &str) -> Option<bool> {
+        self.index.get(wire).map(|&i| self.values[i])
+    }
+
+    /// Sets input bus `prefix`, which must be exactly as wide as `bits`.
+    pub(crate) fn set_bus(&mut self, prefix: &str, bits: &[bool]) -> Result<(), AocError> {
+        let wires: Vec<usize> = bus_wires(&self.names, prefix)?.iter().map(|&wire| self.index[wire]).collect();
+        check_width(prefix, wires.len(), bits.len())?;
+        for (&wire, &bit) in wires.iter().zip(bits) {
+            if self.driven[wire] {
+                return Err(AocError::InvalidInput(format!("wire {} is driven by a gate", self.names[wire])));
             }
+            self.values[wire] = bit;
         }
+        Ok(())
     }
-    dbg!(&circuit);
 
-    circuit
+    /// Reads bus `prefix`, least significant bit first.
+    pub(crate) fn read_bus(&self, prefix: &str) -> Result<Vec<bool>, AocError> {
+        Ok(bus_wires(&self.names, prefix)?
+            .iter()
+            .map(|&wire| self.values[self.index[wire]])
+            .collect())
+    }
+
+    /// Recomputes every gate output from the current inputs.
+    pub(crate) fn evaluate(&mut self) {
+        for &(a, b, gate, output) in &self.gates {
+            let (a, b) = (self.values[a], self.values[b]);
+            self.values[output] = match gate {
+                Gate::And => a && b,
+                Gate::Or => a || b,
+                Gate::Xor => a ^ b,
+            };
+        }
+    }
 }
-#[test]
-fn large_example() {
-    let input = r#"x00: 1
+
+pub(crate) fn parse_input(input: &str) -> Result<Circuit, AocError> {
+    let mut circuit = Circuit::new();
+    let [wires, gates] = &sections(input)[..] else {
+        return Err(AocError::InvalidInput(
+            "expected initial wire values and gates separated by a blank line".to_string(),
+        ));
+    };
+
+    for line in wires {
+        let (wire, value) = line.split_once(": ")?;
+        let value = match value.trim() {
+            "0" => false,
+            "1" => true,
+            _ => return Err(line.error_at(value, format!("invalid wire value {:?}", value))),
+        };
+        circuit.add_initial_wire(wire, value);
+    }
+
+    for line in gates {
+        // Gates look like `x00 AND y00 -> z00`
+        let [input1, gate, input2, "->", output] = line.text.split_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(line.error("expected `<wire> <gate> <wire> -> <wire>`"));
+        };
+        let gate = Gate::from_str(gate).map_err(|message| line.error_at(gate, message))?;
+        circuit.add_gate(input1, input2, gate, output);
+    }
+
+    Ok(circuit)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut circuit = parse_input(input)?;
+        circuit.simulate()?;
+        let z = circuit.read_bus("z")?;
+        let output = from_bits(&z).ok_or_else(|| AocError::InvalidInput("bus z does not fit in 64 bits".to_string()))?;
+        Ok(output.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+const LARGE_EXAMPLE: &str = r#"x00: 1
 x01: 0
 x02: 1
 x03: 1
@@ -183,11 +487,15 @@ hwm AND bqk -> z03
 tgd XOR rvg -> z12
 tnw OR pbm -> gnj"#;
 
-    let mut circuit = parse_input(input);
-    circuit.simulate();
-    let result = circuit.get_output();
-    println!("Output: {}", result);
+#[test]
+fn large_example() {
+    let input = LARGE_EXAMPLE;
+
+    let mut circuit = parse_input(input).unwrap();
+    circuit.simulate().unwrap();
+    assert_eq!(from_bits(&circuit.read_bus("z").unwrap()), Some(2024));
 }
+
 #[test]
 fn test_small() {
     let input = r#"x00: 1
@@ -201,17 +509,118 @@ x00 AND y00 -> z00
 x01 XOR y01 -> z01
 x02 OR y02 -> z02"#;
 
-    let mut circuit = parse_input(input);
-    circuit.simulate();
-    let result = circuit.get_output();
-    println!("Output: {}", result);
-    assert_eq!(result, 0b100);
+    let mut circuit = parse_input(input).unwrap();
+    circuit.simulate().unwrap();
+    assert_eq!(circuit.read_bus("z"), Ok(vec![false, false, true]));
+}
+
+#[test]
+fn test_schedule_errors() {
+    let cycle = "x00: 1\n\nx00 AND b -> a\na OR x00 -> b\na XOR x00 -> z00";
+    assert_eq!(
+        parse_input(cycle).unwrap().simulate(),
+        Err(AocError::InvalidInput("wire a depends on its own value".to_string()))
+    );
+
+    let undriven = "x00: 1\n\nx00 AND y00 -> z00";
+    assert_eq!(
+        parse_input(undriven).unwrap().simulate(),
+        Err(AocError::InvalidInput("wire y00 is never driven".to_string()))
+    );
+
+    let doubled = "x00: 1\ny00: 0\n\nx00 AND y00 -> z00\nx00 OR y00 -> z00";
+    assert_eq!(
+        parse_input(doubled).unwrap().simulate(),
+        Err(AocError::InvalidInput("wire z00 is driven by more than one gate".to_string()))
+    );
 }
+
+#[test]
+fn test_reevaluate() {
+    // A 2-bit adder: z = x + y
+    let input = "x00: 0\nx01: 0\ny00: 0\ny01: 0
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> z01
+x01 AND y01 -> a01
+s01 AND c00 -> b01
+a01 OR b01 -> z02";
+    let mut schedule = parse_input(input).unwrap().schedule().unwrap();
+    for x in 0..4 {
+        for y in 0..4 {
+            schedule.set_bus("x", &to_bits(x, 2)).unwrap();
+            schedule.set_bus("y", &to_bits(y, 2)).unwrap();
+            schedule.evaluate();
+            assert_eq!(schedule.read_bus("z"), Ok(to_bits(x + y, 3)));
+        }
+    }
+    assert!(schedule.set("z00", true).is_err());
+    assert!(schedule.set("x02", true).is_err());
+}
+
+#[test]
+fn test_dot() {
+    let circuit = parse_input(LARGE_EXAMPLE).unwrap();
+    let dot = circuit.to_dot(&["z00".to_string(), "tgd".to_string()]);
+    assert_eq!(dot, include_str!("snapshots/d24_large_example.dot"));
+
+    // Wires that only look like bus wires are left out of the clusters
+    let circuit = parse_input("x00: 1\nn00: 0\név: 1\n\nx00 AND n00 -> ask\nask OR év -> z00\n").unwrap();
+    let clusters: Vec<String> = circuit
+        .to_dot(&[])
+        .lines()
+        .filter(|line| line.contains("subgraph"))
+        .map(str::to_string)
+        .collect();
+    assert_eq!(clusters, ["    subgraph cluster_00 { label=\"bit 00\"; \"x00\"; \"z00\"; }"]);
+}
+
+#[test]
+fn test_buses() {
+    // Not an adder: two custom buses feeding each other
+    let input = "in00: 1\nin01: 0\nin02: 1\nmask: 1
+
+in00 AND mask -> out00
+in01 OR mask -> out01
+in02 XOR mask -> out02
+out00 AND out02 -> flag";
+    let mut circuit = parse_input(input).unwrap();
+    circuit.simulate().unwrap();
+    assert_eq!(circuit.read_bus("out"), Ok(vec![true, true, false]));
+    assert_eq!(circuit.read_bus("in"), Ok(vec![true, false, true]));
+
+    circuit.set_bus("in", &[false, false, false]).unwrap();
+    circuit.simulate().unwrap();
+    assert_eq!(circuit.read_bus("out"), Ok(vec![false, true, true]));
+
+    let err = |message: &str| AocError::InvalidInput(message.to_string());
+    assert_eq!(circuit.set_bus("in", &[true]).unwrap_err(), err("bus in is 3 bits wide, got 1 bits"));
+    assert_eq!(circuit.set_bus("out", &[true; 3]).unwrap_err(), err("wire out00 is driven by a gate"));
+    assert_eq!(circuit.read_bus("z").unwrap_err(), err("no bus named z"));
+
+    let gap = parse_input("z00: 1\nz02: 0\n\nz00 AND z02 -> a").unwrap();
+    assert_eq!(gap.read_bus("z").unwrap_err(), err("bus z is missing bit 1"));
+    let unset = parse_input("x00: 1\n\nx00 AND x00 -> z00").unwrap();
+    assert_eq!(unset.read_bus("z").unwrap_err(), err("wire z00 has no value"));
+}
+
+#[test]
+fn test_bus_bit() {
+    assert_eq!(bus_bit("z07"), Some(('z', 7)));
+    assert_eq!(bus_bit("x100"), Some(('x', 100)));
+    assert_eq!(bus_bit("n00"), None);
+    assert_eq!(bus_bit("z"), None);
+    assert_eq!(bus_bit("zk7"), None);
+    assert_eq!(bus_bit("é00"), None);
+}
+
 #[test]
-fn main() {
-    let input = include_str!("d24.in");
-    let mut circuit = parse_input(input);
-    circuit.simulate();
-    let result = circuit.get_output();
-    println!("Output: {}", result);
+fn test_bits() {
+    assert_eq!(to_bits(0b1011, 5), [true, true, false, true, false]);
+    assert_eq!(from_bits(&to_bits(u64::MAX, 100)), Some(u64::MAX));
+    let mut wide = vec![false; 65];
+    wide[64] = true;
+    assert_eq!(from_bits(&wide), None);
 }
@@ -1 +1,481 @@
+use crate::d24_1::{bus_bit, parse_input, Circuit, Gate, Schedule};
+use crate::rng::Rng;
+use crate::{Answer, AocError, Solution};
+use std::fmt;
 
+fn is_input(wire: &str) -> bool {
+    matches!(bus_bit(wire), Some(('x' | 'y', _)))
+}
+
+fn is_output(wire: &str) -> bool {
+    matches!(bus_bit(wire), Some(('z', _)))
+}
+
+// A fixed adder has exactly this many gates with swapped outputs.
+const SWAPPED_WIRES: usize = 8;
+
+/// A structural rule of the ripple-carry adder that a gate breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Rule {
+    /// The highest z wire is the final carry, which an OR produces.
+    FinalCarryFromOr(Gate),
+    /// Every other z wire is a sum bit, which an XOR produces.
+    SumFromXor(Gate),
+    /// Sum bits past bit 0 combine the half-sum with the carry, not x and y.
+    SumFromCarry,
+    /// `x_i XOR y_i` is a half-sum and has to feed the XOR making `z_i`.
+    HalfSumFeedsXor,
+    /// An XOR of internal wires makes a sum bit, so it has to drive a z wire.
+    XorDrivesZ,
+    /// An AND makes a partial carry, which an OR combines with the other one.
+    CarryFeedsOr,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::FinalCarryFromOr(gate) => write!(f, "the final carry must come from an OR gate, not {}", gate),
+            Rule::SumFromXor(gate) => write!(f, "a sum bit must come from an XOR gate, not {}", gate),
+            Rule::SumFromCarry => write!(f, "a sum bit must XOR the half-sum with the carry, not x with y"),
+            Rule::HalfSumFeedsXor => write!(f, "the half-sum x XOR y must feed an XOR gate"),
+            Rule::XorDrivesZ => write!(f, "an XOR of internal wires is a sum bit and must drive a z wire"),
+            Rule::CarryFeedsOr => write!(f, "a partial carry from an AND gate must feed an OR gate"),
+        }
+    }
+}
+
+/// A gate output that breaks one of the adder's rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Miswiring {
+    pub(crate) wire: String,
+    pub(crate) rule: Rule,
+}
+
+impl fmt::Display for Miswiring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.wire, self.rule)
+    }
+}
+
+/// Checks every gate against the full-adder structure. In a ripple-carry
+/// adder every gate has a fixed role, so a gate whose output feeds the wrong
+/// kind of gate (or the wrong z wire) must have been swapped.
+pub(crate) fn validate(circuit: &Circuit) -> Vec<Miswiring> {
+    // By bit number, so z100 comes after z99
+    let last_z = circuit
+        .gates
+        .iter()
+        .filter_map(|(_, _, _, output)| bus_bit(output).filter(|&(bus, _)| bus == 'z').map(|(_, bit)| (bit, output)))
+        .max()
+        .map(|(_, output)| output.clone())
+        .unwrap_or_default();
+
+    let feeds = |wire: &str, kind: Gate| {
+        circuit
+            .gates
+            .iter()
+            .any(|(a, b, gate, _)| *gate == kind && (a == wire || b == wire))
+    };
+
+    let mut miswirings = Vec::new();
+    for (a, b, gate, output) in &circuit.gates {
+        let first_bit = [a, b].iter().all(|wire| bus_bit(wire).is_some_and(|(_, bit)| bit == 0));
+
+        let broken = if *output == last_z {
+            (*gate != Gate::Or).then_some(Rule::FinalCarryFromOr(*gate))
+        } else if is_output(output) {
+            if *gate != Gate::Xor {
+                Some(Rule::SumFromXor(*gate))
+            } else {
+                // Bit 0 has no carry in, so its sum is x00 XOR y00
+                ((is_input(a) || is_input(b)) && !first_bit).then_some(Rule::SumFromCarry)
+            }
+        } else {
+            match gate {
+                Gate::Xor if is_input(a) && is_input(b) => (!feeds(output, Gate::Xor)).then_some(Rule::HalfSumFeedsXor),
+                Gate::Xor => Some(Rule::XorDrivesZ),
+                // The carry out of bit 0 is x00 AND y00 on its own
+                Gate::And => (!first_bit && !feeds(output, Gate::Or)).then_some(Rule::CarryFeedsOr),
+                Gate::Or => None,
+            }
+        };
+
+        if let Some(rule) = broken {
+            miswirings.push(Miswiring {
+                wire: output.clone(),
+                rule,
+            });
+        }
+    }
+
+    miswirings.sort_by(|l, r| l.wire.cmp(&r.wire));
+    miswirings
+}
+
+fn flagged_wires(circuit: &Circuit) -> Vec<String> {
+    let mut flagged: Vec<String> = validate(circuit).into_iter().map(|m| m.wire).collect();
+    flagged.dedup();
+    flagged
+}
+
+// The puzzle swaps exactly four pairs, so flagging any other number of
+// wires means the rules missed a swap or caught a correct gate.
+fn find_swapped_wires(circuit: &Circuit) -> Result<Vec<String>, AocError> {
+    let flagged = flagged_wires(circuit);
+    if flagged.len() != SWAPPED_WIRES {
+        return Err(AocError::InvalidInput(format!(
+            "expected {} miswired wires, found {}: {}",
+            SWAPPED_WIRES,
+            flagged.len(),
+            flagged.join(",")
+        )));
+    }
+    Ok(flagged)
+}
+
+/// The circuit as Graphviz DOT, with every miswired gate drawn in red.
+pub(crate) fn highlighted_dot(circuit: &Circuit) -> String {
+    circuit.to_dot(&flagged_wires(circuit))
+}
+
+/// Inputs for which the circuit's z bus is not x + y.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Counterexample {
+    /// Lowest z bit that differs from the true sum.
+    pub(crate) bit: usize,
+    pub(crate) x: Vec<bool>,
+    pub(crate) y: Vec<bool>,
+    pub(crate) expected: Vec<bool>,
+    pub(crate) actual: Vec<bool>,
+}
+
+// Most significant bit first, like a binary literal.
+fn binary(bits: &[bool]) -> String {
+    bits.iter().rev().map(|&bit| if bit { '1' } else { '0' }).collect()
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "z{:02} is wrong for x = {}, y = {}: expected z = {}, got {}",
+            self.bit,
+            binary(&self.x),
+            binary(&self.y),
+            binary(&self.expected),
+            binary(&self.actual)
+        )
+    }
+}
+
+// x + y, one bit wider than the operands.
+fn add(x: &[bool], y: &[bool]) -> Vec<bool> {
+    let mut carry = false;
+    let mut sum: Vec<bool> = x
+        .iter()
+        .zip(y)
+        .map(|(&a, &b)| {
+            let bit = a ^ b ^ carry;
+            carry = (a && b) || (carry && (a ^ b));
+            bit
+        })
+        .collect();
+    sum.push(carry);
+    sum
+}
+
+// Operands that exercise the carry chain: zero, all ones, a carry out of
+// every single bit and alternating patterns that carry nowhere or everywhere.
+fn edge_cases(width: usize) -> Vec<(Vec<bool>, Vec<bool>)> {
+    let zeros = vec![false; width];
+    let ones = vec![true; width];
+    let alternating: Vec<bool> = (0..width).map(|i| i % 2 == 0).collect();
+    let inverted: Vec<bool> = alternating.iter().map(|&bit| !bit).collect();
+    let one = |i: usize| (0..width).map(|j| j == i).collect::<Vec<bool>>();
+
+    let mut cases = vec![
+        (zeros.clone(), zeros.clone()),
+        (ones.clone(), zeros.clone()),
+        (zeros.clone(), ones.clone()),
+        (ones.clone(), ones.clone()),
+        (ones.clone(), one(0)),
+        (alternating.clone(), inverted.clone()),
+        (alternating.clone(), alternating.clone()),
+        (inverted.clone(), inverted),
+    ];
+    for i in 0..width {
+        cases.push((one(i), zeros.clone()));
+        cases.push((zeros.clone(), one(i)));
+        cases.push((one(i), one(i)));
+    }
+    cases
+}
+
+// (expected, actual) z bus
+type Mismatch = (Vec<bool>, Vec<bool>);
+
+// Runs one case, returning the true sum and the circuit's if they differ.
+fn run_case(schedule: &mut Schedule, x: &[bool], y: &[bool]) -> Result<Option<Mismatch>, AocError> {
+    schedule.set_bus("x", x)?;
+    schedule.set_bus("y", y)?;
+    schedule.evaluate();
+    let actual = schedule.read_bus("z")?;
+    let mut expected = add(x, y);
+    expected.resize(actual.len(), false);
+    Ok((actual != expected).then_some((expected, actual)))
+}
+
+/// Checks that the circuit adds its x and y buses into z, first on edge
+/// cases and then on `random_cases` random operands drawn from `seed`.
+///
+/// A failing case is shrunk by clearing set bits of x and y for as long as
+/// the sum stays wrong, so the counterexample has no bit that could be
+/// dropped.
+pub(crate) fn check_adder(circuit: &Circuit, random_cases: usize, seed: u64) -> Result<Option<Counterexample>, AocError> {
+    let mut schedule = circuit.schedule()?;
+    let width = schedule.read_bus("x")?.len();
+    // A narrower z would drop the final carry and still look right
+    let z_width = schedule.read_bus("z")?.len();
+    if z_width <= width {
+        return Err(AocError::InvalidInput(format!("z has {} bits, but x + y needs {}", z_width, width + 1)));
+    }
+    let mut rng = Rng::new(seed);
+    let random = (0..random_cases).map(|_| (rng.bits(width), rng.bits(width)));
+
+    let mut failing = None;
+    for (x, y) in edge_cases(width).into_iter().chain(random) {
+        if run_case(&mut schedule, &x, &y)?.is_some() {
+            failing = Some((x, y));
+            break;
+        }
+    }
+    let Some((x, y)) = failing else {
+        return Ok(None);
+    };
+
+    let mut operands = [x, y];
+    let mut shrunk = true;
+    while shrunk {
+        shrunk = false;
+        for (which, bit) in (0..2).flat_map(|which| (0..width).rev().map(move |bit| (which, bit))) {
+            if !operands[which][bit] {
+                continue;
+            }
+            operands[which][bit] = false;
+            if run_case(&mut schedule, &operands[0], &operands[1])?.is_some() {
+                shrunk = true;
+            } else {
+                operands[which][bit] = true;
+            }
+        }
+    }
+
+    let [x, y] = operands;
+    let (expected, actual) = run_case(&mut schedule, &x, &y)?
+        .ok_or_else(|| AocError::InvalidState("the failing case passed once shrunk".to_string()))?;
+    let bit = expected.iter().zip(&actual).position(|(e, a)| e != a).unwrap_or_default();
+    Ok(Some(Counterexample {
+        bit,
+        x,
+        y,
+        expected,
+        actual,
+    }))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        24
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let circuit = parse_input(input)?;
+        Ok(find_swapped_wires(&circuit)?.join(",").into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::d24_1::to_bits;
+
+    // Builds the puzzle text for a correct `bits`-wide ripple-carry adder,
+    // then swaps the outputs of each pair of named wires.
+    fn adder(bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut gates = vec![
+            ("x00".to_string(), "XOR", "y00".to_string(), "z00".to_string()),
+            ("x00".to_string(), "AND", "y00".to_string(), "c00".to_string()),
+        ];
+        for i in 1..bits {
+            let carry_in = format!("c{:02}", i - 1);
+            let carry_out = if i == bits - 1 {
+                format!("z{:02}", bits)
+            } else {
+                format!("c{:02}", i)
+            };
+            gates.push((format!("x{:02}", i), "XOR", format!("y{:02}", i), format!("s{:02}", i)));
+            gates.push((format!("x{:02}", i), "AND", format!("y{:02}", i), format!("a{:02}", i)));
+            gates.push((format!("s{:02}", i), "XOR", carry_in.clone(), format!("z{:02}", i)));
+            gates.push((format!("s{:02}", i), "AND", carry_in, format!("b{:02}", i)));
+            gates.push((format!("a{:02}", i), "OR", format!("b{:02}", i), carry_out));
+        }
+
+        for &(p, q) in swaps {
+            for gate in gates.iter_mut() {
+                if gate.3 == p {
+                    gate.3 = q.to_string();
+                } else if gate.3 == q {
+                    gate.3 = p.to_string();
+                }
+            }
+        }
+
+        let mut text = String::new();
+        for i in 0..bits {
+            text += &format!("x{:02}: 0\n", i);
+        }
+        for i in 0..bits {
+            text += &format!("y{:02}: 0\n", i);
+        }
+        text.push('\n');
+        for (a, gate, b, out) in gates {
+            text += &format!("{} {} {} -> {}\n", a, gate, b, out);
+        }
+        text
+    }
+
+    #[test]
+    fn test_correct_adder_has_no_swaps() {
+        let circuit = parse_input(&adder(45, &[])).unwrap();
+        assert!(validate(&circuit).is_empty());
+        assert_eq!(
+            find_swapped_wires(&circuit),
+            Err(AocError::InvalidInput("expected 8 miswired wires, found 0: ".to_string()))
+        );
+
+        // Past 100 bits the final carry is z100 or later, not z99
+        let circuit = parse_input(&adder(120, &[])).unwrap();
+        assert!(validate(&circuit).is_empty());
+    }
+
+    #[test]
+    fn test_finds_four_swapped_pairs() {
+        let swaps = [("z05", "c05"), ("z10", "b10"), ("s15", "a15"), ("z20", "s20")];
+        let circuit = parse_input(&adder(45, &swaps)).unwrap();
+        assert_eq!(
+            find_swapped_wires(&circuit).unwrap().join(","),
+            "a15,b10,c05,s15,s20,z05,z10,z20"
+        );
+    }
+
+    #[test]
+    fn test_explains_each_miswiring() {
+        let swaps = [("z05", "c05"), ("z10", "b10"), ("s15", "a15"), ("z20", "s20")];
+        let circuit = parse_input(&adder(45, &swaps)).unwrap();
+        let explanations: Vec<String> = validate(&circuit).iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            explanations,
+            [
+                "a15: the half-sum x XOR y must feed an XOR gate",
+                "b10: an XOR of internal wires is a sum bit and must drive a z wire",
+                "c05: an XOR of internal wires is a sum bit and must drive a z wire",
+                "s15: a partial carry from an AND gate must feed an OR gate",
+                "s20: an XOR of internal wires is a sum bit and must drive a z wire",
+                "z05: a sum bit must come from an XOR gate, not OR",
+                "z10: a sum bit must come from an XOR gate, not AND",
+                "z20: a sum bit must XOR the half-sum with the carry, not x with y",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sum_from_carry_either_side() {
+        // z07 XORs x07 straight from the input instead of the half-sum,
+        // whichever side of the gate it is written on
+        let correct = adder(8, &[]);
+        for gate in ["x07 XOR c06 -> z07", "c06 XOR x07 -> z07"] {
+            let circuit = parse_input(&correct.replace("s07 XOR c06 -> z07", gate)).unwrap();
+            let rules: Vec<Rule> = validate(&circuit).into_iter().filter(|m| m.wire == "z07").map(|m| m.rule).collect();
+            assert_eq!(rules, [Rule::SumFromCarry], "{}", gate);
+        }
+    }
+
+    #[test]
+    fn test_highlighted_dot() {
+        let circuit = parse_input(&adder(8, &[("z03", "c03")])).unwrap();
+        let dot = highlighted_dot(&circuit);
+        let red: Vec<&str> = dot.lines().filter(|line| line.contains("color=red")).collect();
+        assert_eq!(
+            red,
+            [
+                "    \"g_c03\" [label=\"XOR\", color=red, fontcolor=red];",
+                "    \"g_z03\" [label=\"OR\", color=red, fontcolor=red];",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_adder() {
+        let swaps = [("z05", "c05"), ("z10", "b10"), ("s15", "a15")];
+        let circuit = parse_input(&adder(45, &swaps)).unwrap();
+        let counterexample = check_adder(&circuit, 100, 1).unwrap().unwrap();
+        assert_eq!(counterexample.bit, 5);
+        assert_eq!(counterexample.x.iter().chain(&counterexample.y).filter(|&&bit| bit).count(), 1);
+
+        // Undoing the swaps fixes the adder, and so does swapping the two
+        // partial carries that feed the same OR
+        let fixed = circuit.with_swaps(&swaps).unwrap();
+        assert_eq!(check_adder(&fixed, 100, 1), Ok(None));
+        let harmless = fixed.with_swaps(&[("a30", "b30")]).unwrap();
+        assert_eq!(check_adder(&harmless, 100, 1), Ok(None));
+        let broken = fixed.with_swaps(&[("z30", "c30")]).unwrap();
+        assert_eq!(check_adder(&broken, 100, 1).unwrap().unwrap().bit, 30);
+
+        // Swapping z20 and s20 feeds s20 back into itself, so it can't be evaluated
+        let looped = fixed.with_swaps(&[("z20", "s20")]).unwrap();
+        assert_eq!(
+            check_adder(&looped, 100, 1),
+            Err(AocError::InvalidInput("wire s20 depends on its own value".to_string()))
+        );
+        assert!(fixed.with_swaps(&[("x00", "z00")]).is_err());
+
+        // Without z04 the final carry goes nowhere
+        let narrow = parse_input(&adder(4, &[]).replace("-> z04", "-> c03")).unwrap();
+        assert_eq!(
+            check_adder(&narrow, 100, 1),
+            Err(AocError::InvalidInput("z has 4 bits, but x + y needs 5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_counterexample_display() {
+        let circuit = parse_input(&adder(4, &[("z02", "b02")])).unwrap();
+        assert_eq!(
+            check_adder(&circuit, 0, 1).unwrap().unwrap().to_string(),
+            "z02 is wrong for x = 0100, y = 0000: expected z = 00100, got 01000"
+        );
+    }
+
+    #[test]
+    fn test_wide_adder() {
+        // 100 bits: (2^100 - 1) + 1 carries all the way to z100
+        let mut circuit = parse_input(&adder(100, &[])).unwrap();
+        circuit.set_bus("x", &[true; 100]).unwrap();
+        circuit.set_bus("y", &to_bits(1, 100)).unwrap();
+        circuit.simulate().unwrap();
+
+        let mut expected = vec![false; 101];
+        expected[100] = true;
+        assert_eq!(circuit.read_bus("z"), Ok(expected));
+    }
+}
@@ -1,5 +1,5 @@
-use std::io::{self, BufRead};
-
+use crate::error::non_empty_lines;
+use crate::{Answer, AocError, Solution};
 fn is_safe_without_dampener(levels: &[i32]) -> bool {
     if levels.len() < 2 {
         return true;
@@ -10,7 +10,7 @@ fn is_safe_without_dampener(levels: &[i32]) -> bool {
     let first_diff = (levels[1] - levels[0]).abs();
 
     // First difference must be between 1 and 3
-    if first_diff < 1 || first_diff > 3 {
+    if !(1..=3).contains(&first_diff) {
         return false;
     }
 
@@ -50,25 +50,35 @@ fn is_safe_with_dampener(levels: &[i32]) -> bool {
 
     false
 }
-#[test]
-fn main() -> io::Result<()> {
-    let stdin = io::stdin();
-    let mut safe_count = 0;
-
-    for line in stdin.lock().lines() {
-        let line = line?;
-        let levels: Vec<i32> = line
-            .split_whitespace()
-            .filter_map(|s| s.parse().ok())
-            .collect();
-
-        if is_safe_with_dampener(&levels) {
-            safe_count += 1;
-        }
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part(&self) -> u32 {
+        2
     }
 
-    println!("Number of safe reports: {}", safe_count);
-    Ok(())
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let mut safe_count = 0;
+
+        for line in non_empty_lines(input) {
+            let levels: Vec<i32> = line
+                .text
+                .split_whitespace()
+                .map(|level| line.parse(level))
+                .collect::<Result<_, _>>()?;
+
+            if is_safe_with_dampener(&levels) {
+                safe_count += 1;
+            }
+        }
+
+        Ok(safe_count.into())
+    }
 }
 
 #[cfg(test)]
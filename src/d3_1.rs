@@ -1,9 +1,18 @@
-use std::fs::read_to_string;
-#[test]
-fn main() {
-    let input = read_to_string("./src/d3.in").expect("Failed to read input file");
-    let result = process_input(&input);
-    println!("Sum of all multiplication results: {}", result);
+use crate::{Answer, AocError, Solution};
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(process_input(input).into())
+    }
 }
 
 fn process_input(input: &str) -> i32 {
@@ -80,4 +89,4 @@ mod tests {
         let input = "mul(44,46)mul(123,4)";
         assert_eq!(process_input(input), 2024 + 492);
     }
-}
\ No newline at end of file
+}
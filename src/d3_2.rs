@@ -1,9 +1,18 @@
-use std::fs::read_to_string;
-#[test]
-fn main() {
-    let input = read_to_string("./src/d3.in").expect("Failed to read input file");
-    let result = process_input(&input);
-    println!("Sum of all enabled multiplication results: {}", result);
+use crate::{Answer, AocError, Solution};
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(process_input(input).into())
+    }
 }
 
 fn process_input(input: &str) -> i32 {
@@ -100,4 +109,4 @@ mod tests {
         let input = "mul(2,2)";
         assert_eq!(process_input(input), 4);  // multiplication starts enabled
     }
-}
\ No newline at end of file
+}
@@ -1,51 +1,33 @@
-use std::io::{self, BufRead};
-#[test]
-fn main() {
-    // Read input from stdin
-    let stdin = io::stdin();
-    let lines: Vec<String> = stdin
-        .lock()
-        .lines()
-        .filter_map(Result::ok)
-        .filter(|line| !line.trim().is_empty())
-        .collect();
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
+pub struct Solver;
 
-    let grid: Vec<Vec<char>> = lines
-        .iter()
-        .map(|line| line.chars().collect())
-        .collect();
-
-    let count = count_xmas_patterns(&grid);
-    println!("Found {} X-MAS patterns", count);
-}
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        4
+    }
 
-fn count_xmas_patterns(grid: &[Vec<char>]) -> usize {
-    let rows = grid.len();
-    if rows < 3 {
-        return 0;
+    fn part(&self) -> u32 {
+        2
     }
-    let cols = grid[0].len();
-    if cols < 3 {
-        return 0;
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(count_xmas_patterns(&Grid::parse(input)?).into())
     }
+}
 
+fn count_xmas_patterns(grid: &Grid<char>) -> usize {
     let mut count = 0;
 
     // Check each possible center point of the X
-    for row in 1..rows-1 {
-        for col in 1..cols-1 {
-            if grid[row][col] == 'A' {
-                let top_left = get_char(grid, row - 1, col - 1);
-                let top_right = get_char(grid, row - 1, col + 1);
-                let bottom_left = get_char(grid, row + 1, col - 1);
-                let bottom_right = get_char(grid, row + 1, col + 1);
+    for center in grid.find_all(&'A') {
+        let corner = |dx, dy| grid.get(center + Point::new(dx, dy)).copied();
 
-                // For each pair of diagonals, check if they form valid MAS patterns
-                if let (Some(tl), Some(tr), Some(bl), Some(br)) = (top_left, top_right, bottom_left, bottom_right) {
-                    if is_valid_xmas(tl, tr, bl, br) {
-                        count += 1;
-                    }
-                }
+        // Centers on the edge are missing a corner and can't form an X
+        if let (Some(tl), Some(tr), Some(bl), Some(br)) = (corner(-1, -1), corner(1, -1), corner(-1, 1), corner(1, 1)) {
+            if is_valid_xmas(tl, tr, bl, br) {
+                count += 1;
             }
         }
     }
@@ -53,10 +35,6 @@ fn count_xmas_patterns(grid: &[Vec<char>]) -> usize {
     count
 }
 
-fn get_char(grid: &[Vec<char>], row: usize, col: usize) -> Option<char> {
-    grid.get(row).and_then(|r| r.get(col)).copied()
-}
-
 fn is_valid_xmas(top_left: char, top_right: char, bottom_left: char, bottom_right: char) -> bool {
     // Check all possible combinations of MAS patterns
     is_valid_diagonal(top_left, bottom_right) && is_valid_diagonal(top_right, bottom_left)
@@ -73,29 +51,31 @@ mod tests {
 
     #[test]
     fn test_example_grid() {
-        let input = vec![
-            ".M.S......".chars().collect(),
-            "..A..MSMS.".chars().collect(),
-            ".M.S.MAA..".chars().collect(),
-            "..A.ASMSM.".chars().collect(),
-            ".M.S.M....".chars().collect(),
-            "..........".chars().collect(),
-            "S.S.S.S.S.".chars().collect(),
-            ".A.A.A.A..".chars().collect(),
-            "M.M.M.M.M.".chars().collect(),
-            "..........".chars().collect(),
-        ];
+        let input = Grid::parse(
+            ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+..........",
+        )
+        .unwrap();
         
         assert_eq!(count_xmas_patterns(&input), 9);
     }
 
     #[test]
     fn test_simple_xmas() {
-        let input = vec![
-            "M.S".chars().collect(),
-            ".A.".chars().collect(),
-            "M.S".chars().collect(),
-        ];
+        let input = Grid::parse(
+            "M.S
+.A.
+M.S",
+        )
+        .unwrap();
         
         assert_eq!(count_xmas_patterns(&input), 1);
     }
@@ -107,4 +87,4 @@ mod tests {
         assert!(!is_valid_diagonal('M', 'M')); // Invalid
         assert!(!is_valid_diagonal('S', 'S')); // Invalid
     }
-}
\ No newline at end of file
+}
@@ -1,33 +1,34 @@
+use crate::error::sections;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet, VecDeque};
 
-fn parse_input(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    let mut parts = input.trim().split("\n\n");
+// Each rule `(a, b)` says page `a` has to come before page `b`.
+type Rules = Vec<(u32, u32)>;
+
+fn parse_input(input: &str) -> Result<(Rules, Vec<Vec<u32>>), AocError> {
+    let sections = sections(input);
+    let [rule_lines, update_lines] = sections.as_slice() else {
+        return Err(AocError::InvalidInput(
+            "expected ordering rules and updates separated by a blank line".to_string(),
+        ));
+    };
     
     // Parse rules
-    let rules: Vec<(u32, u32)> = parts
-        .next()
-        .unwrap()
-        .lines()
+    let rules = rule_lines
+        .iter()
         .map(|line| {
-            let mut nums = line.split('|')
-                .map(|n| n.parse::<u32>().unwrap());
-            (nums.next().unwrap(), nums.next().unwrap())
+            let (before, after) = line.split_once("|")?;
+            Ok((line.parse(before)?, line.parse(after)?))
         })
-        .collect();
+        .collect::<Result<_, AocError>>()?;
     
     // Parse updates
-    let updates: Vec<Vec<u32>> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| {
-            line.split(',')
-                .map(|n| n.parse::<u32>().unwrap())
-                .collect()
-        })
-        .collect();
+    let updates = update_lines
+        .iter()
+        .map(|line| line.text.split(',').map(|n| line.parse(n)).collect())
+        .collect::<Result<_, AocError>>()?;
     
-    (rules, updates)
+    Ok((rules, updates))
 }
 
 fn build_graph(rules: &[(u32, u32)], pages: &[u32]) -> HashMap<u32, Vec<u32>> {
@@ -51,7 +52,7 @@ fn build_graph(rules: &[(u32, u32)], pages: &[u32]) -> HashMap<u32, Vec<u32>> {
 fn calculate_in_degrees(graph: &HashMap<u32, Vec<u32>>, pages: &[u32]) -> HashMap<u32, u32> {
     let mut in_degrees: HashMap<u32, u32> = pages.iter().map(|&p| (p, 0)).collect();
     
-    for (_node, edges) in graph {
+    for edges in graph.values() {
         for &dest in edges {
             *in_degrees.entry(dest).or_default() += 1;
         }
@@ -75,7 +76,9 @@ fn is_valid_order(rules: &[(u32, u32)], update: &[u32]) -> bool {
         
         if let Some(edges) = graph.get(&node) {
             for &dest in edges {
-                let deg = in_degrees.get_mut(&dest).unwrap();
+                let Some(deg) = in_degrees.get_mut(&dest) else {
+                    continue;
+                };
                 *deg -= 1;
                 if *deg == 0 {
                     queue.push_back(dest);
@@ -87,27 +90,40 @@ fn is_valid_order(rules: &[(u32, u32)], update: &[u32]) -> bool {
     // Check if we got all nodes and they match the original order
     result.len() == update.len() && 
         update.iter().enumerate().all(|(i, &page)| {
-            let pos_in_result = result.iter().position(|&x| x == page).unwrap();
-            pos_in_result <= i
+            result.iter().position(|&x| x == page).is_some_and(|pos_in_result| pos_in_result <= i)
         })
 }
 
 fn get_middle_number(update: &[u32]) -> u32 {
     update[update.len() / 2]
 }
-#[test]
-fn main() {
-    let input = include_str!("d5.in");
-    let (rules, updates) = parse_input(input);
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let (rules, updates) = parse_input(input)?;
     
-    let mut sum = 0;
-    for update in &updates {
-        if is_valid_order(&rules, update) {
-            sum += get_middle_number(update);
+        let mut sum = 0;
+        for update in &updates {
+            if is_valid_order(&rules, update) {
+                sum += get_middle_number(update);
+            }
         }
+        Ok(sum.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
     }
-    
-    println!("Sum of middle numbers from valid updates: {}", sum);
 }
 
 #[cfg(test)]
@@ -145,7 +161,7 @@ mod tests {
 61,13,29
 97,13,75,29,47";
         
-        let (rules, updates) = parse_input(input);
+        let (rules, updates) = parse_input(input).unwrap();
         let mut sum = 0;
         for update in &updates {
             if is_valid_order(&rules, update) {
@@ -154,4 +170,9 @@ mod tests {
         }
         assert_eq!(sum, 143);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_unorderable_update() {
+        // An update caught in a cycle of rules is simply not in order
+        assert_eq!(Solver.solve("1|2\n2|1\n4|5\n5|6\n4|6\n\n1,2,3\n4,5,6"), Ok(5u32.into()));
+    }
+}
@@ -1,104 +1,172 @@
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::HashSet;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+// Returns the map with the guard's cell cleared, plus where the guard starts.
+pub(crate) fn parse_map(input: &str) -> Result<(Grid<char>, Point, Direction), AocError> {
+    let mut grid = Grid::parse(input)?;
+    let (start_pos, start_dir) = grid
+        .iter()
+        .find_map(|(pos, &cell)| Direction::try_from(cell).ok().map(|dir| (pos, dir)))
+        .ok_or_else(|| AocError::InvalidInput("no guard on the map".to_string()))?;
+    grid.set(start_pos, '.');
+    Ok((grid, start_pos, start_dir))
+}
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+/// The guard's route in order: each entry is a cell and the direction the
+/// guard faces there, so a turn repeats the cell with the new direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Trajectory {
+    pub(crate) steps: Vec<(Point, Direction)>,
+    /// Whether the guard ended up walking in a loop instead of leaving.
+    pub(crate) loops: bool,
 }
 
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
+impl Trajectory {
+    /// Where the guard turned, in order.
+    pub(crate) fn turns(&self) -> Vec<Point> {
+        self.steps
+            .windows(2)
+            .filter(|pair| pair[0].0 == pair[1].0)
+            .map(|pair| pair[0].0)
+            .collect()
     }
 
-    fn next_position(&self, pos: (i32, i32)) -> (i32, i32) {
-        let (x, y) = pos;
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Right => (x + 1, y),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-        }
+    /// Number of distinct cells the guard stood on.
+    pub(crate) fn visited(&self) -> usize {
+        self.steps.iter().map(|&(pos, _)| pos).collect::<HashSet<_>>().len()
     }
 }
 
-struct Grid {
-    cells: Vec<Vec<char>>,
-    rows: i32,
-    cols: i32,
-}
+/// Walks the guard until it leaves the map or repeats a state. `obstruction`
+/// is an extra obstacle, as placed in part 2.
+pub(crate) fn simulate_guard_path(
+    grid: &Grid<char>,
+    start_pos: Point,
+    start_dir: Direction,
+    obstruction: Option<Point>,
+) -> Trajectory {
+    let (mut pos, mut direction) = (start_pos, start_dir);
+    let mut steps = vec![(pos, direction)];
+    let mut seen = HashSet::from([(pos, direction)]);
+
+    loop {
+        let next_pos = pos + direction;
 
-impl Grid {
-    fn new(input: &str) -> (Self, (i32, i32), Direction) {
-        let mut cells: Vec<Vec<char>> = input
-            .lines()
-            .map(|line| line.chars().collect())
-            .collect();
-        
-        let rows = cells.len() as i32;
-        let cols = cells[0].len() as i32;
-        let mut start_pos = (0, 0);
-        let mut start_dir = Direction::Up;
-
-        // Find starting position and direction
-        for (y, row) in cells.iter_mut().enumerate() {
-            for (x, cell) in row.iter_mut().enumerate() {
-                if *cell == '^' {
-                    start_pos = (x as i32, y as i32);
-                    start_dir = Direction::Up;
-                    *cell = '.';
-                    break;
-                }
-            }
+        let Some(&cell) = grid.get(next_pos) else {
+            return Trajectory { steps, loops: false };
+        };
+
+        if cell == '#' || Some(next_pos) == obstruction {
+            direction = direction.turn_right();
+        } else {
+            pos = next_pos;
+        }
+        if !seen.insert((pos, direction)) {
+            return Trajectory { steps, loops: true };
         }
+        steps.push((pos, direction));
+    }
+}
 
-        (
-            Grid { cells, rows, cols },
-            start_pos,
-            start_dir,
-        )
+// Path markers as in the puzzle statement: `|` and `-` for cells crossed
+// one way, `+` where the path turns or crosses itself. The start keeps the
+// guard's arrow and the obstruction is drawn as `O`. `guard` is drawn last,
+// for the frame being shown.
+fn draw(
+    grid: &Grid<char>,
+    steps: &[(Point, Direction)],
+    obstruction: Option<Point>,
+    guard: Option<(Point, Direction)>,
+    color: bool,
+) -> String {
+    let mut map = grid.clone();
+    for &(pos, direction) in steps {
+        let marker = match direction {
+            Direction::Up | Direction::Down => '|',
+            Direction::Left | Direction::Right => '-',
+        };
+        map[pos] = match map[pos] {
+            '.' => marker,
+            current if current == marker => marker,
+            _ => '+',
+        };
+    }
+    if let Some(&(pos, direction)) = steps.first() {
+        map[pos] = direction.glyph();
+    }
+    if let Some(pos) = obstruction {
+        map.set(pos, 'O');
+    }
+    if let Some((pos, direction)) = guard {
+        map[pos] = direction.glyph();
     }
 
-    fn is_valid_position(&self, pos: (i32, i32)) -> bool {
-        let (x, y) = pos;
-        x >= 0 && x < self.cols && y >= 0 && y < self.rows
+    if !color {
+        return map.to_string();
     }
+    map.map(|&cell| match cell {
+        '|' | '-' | '+' => format!("\x1b[33m{}\x1b[0m", cell),
+        'O' => format!("\x1b[1;31m{}\x1b[0m", cell),
+        '^' | '>' | 'v' | '<' => format!("\x1b[1;32m{}\x1b[0m", cell),
+        _ => cell.to_string(),
+    })
+    .to_string()
+}
 
-    fn is_obstacle(&self, pos: (i32, i32)) -> bool {
-        let (x, y) = pos;
-        self.cells[y as usize][x as usize] == '#'
+/// The whole trajectory drawn over the map, with ANSI colors if `color`.
+pub(crate) fn render(grid: &Grid<char>, trajectory: &Trajectory, obstruction: Option<Point>, color: bool) -> String {
+    draw(grid, &trajectory.steps, obstruction, None, color)
+}
+
+/// One drawing per step, with the guard's arrow where it currently stands.
+pub(crate) fn frames<'a>(
+    grid: &'a Grid<char>,
+    trajectory: &'a Trajectory,
+    obstruction: Option<Point>,
+    color: bool,
+) -> impl Iterator<Item = String> + 'a {
+    (1..=trajectory.steps.len()).map(move |n| {
+        let steps = &trajectory.steps[..n];
+        draw(grid, steps, obstruction, steps.last().copied(), color)
+    })
+}
+
+/// Plays `frames` back on a terminal, clearing the screen before each one.
+pub(crate) fn play(frames: impl IntoIterator<Item = String>, delay: Duration, out: &mut impl Write) -> io::Result<()> {
+    for frame in frames {
+        writeln!(out, "\x1b[H\x1b[2J{}", frame)?;
+        out.flush()?;
+        thread::sleep(delay);
     }
+    Ok(())
 }
 
-fn simulate_guard_path(input: &str) -> usize {
-    let (grid, mut pos, mut direction) = Grid::new(input);
-    let mut visited = HashSet::new();
-    visited.insert(pos);
+pub struct Solver;
 
-    loop {
-        let next_pos = direction.next_position(pos);
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        6
+    }
 
-        if !grid.is_valid_position(next_pos) {
-            break;
-        }
+    fn part(&self) -> u32 {
+        1
+    }
 
-        if grid.is_obstacle(next_pos) {
-            direction = direction.turn_right();
-        } else {
-            pos = next_pos;
-            visited.insert(pos);
-        }
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let (grid, start_pos, start_dir) = parse_map(input)?;
+        Ok(simulate_guard_path(&grid, start_pos, start_dir, None).visited().into())
     }
 
-    visited.len()
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_map(input).map(drop)
+    }
 }
+
 #[test]
 fn sample() {
     let example_input = "....#.....
@@ -112,14 +180,73 @@ fn sample() {
 #.........
 ......#...";
 
-    let result = simulate_guard_path(example_input);
+    let (grid, start_pos, start_dir) = parse_map(example_input).unwrap();
+    let result = simulate_guard_path(&grid, start_pos, start_dir, None).visited();
     println!("Number of distinct positions visited: {}", result);
     assert_eq!(result, 41, "Expected 41 positions, but got {}", result);
 }
 
 #[test]
-fn main(){
-    let input = include_str!("d6.in");
-    let result = simulate_guard_path(input);
-    println!("Number of distinct positions visited: {}", result);
-}
\ No newline at end of file
+fn render_obstructions() {
+    let example_input = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+    let (grid, start_pos, start_dir) = parse_map(example_input).unwrap();
+
+    let trajectory = simulate_guard_path(&grid, start_pos, start_dir, None);
+    assert!(!trajectory.loops);
+    assert_eq!(trajectory.turns()[..3], [Point::new(4, 1), Point::new(8, 1), Point::new(8, 6)]);
+
+    // The second and third loops from the puzzle statement
+    let obstruction = Some(Point::new(6, 7));
+    let trajectory = simulate_guard_path(&grid, start_pos, start_dir, obstruction);
+    assert!(trajectory.loops);
+    assert_eq!(
+        render(&grid, &trajectory, obstruction, false),
+        "....#.....
+....+---+#
+....|...|.
+..#.|...|.
+..+-+-+#|.
+..|.|.|.|.
+.#+-^-+-+.
+......O.#.
+#.........
+......#..."
+    );
+
+    let obstruction = Some(Point::new(7, 7));
+    let trajectory = simulate_guard_path(&grid, start_pos, start_dir, obstruction);
+    assert_eq!(
+        render(&grid, &trajectory, obstruction, false),
+        "....#.....
+....+---+#
+....|...|.
+..#.|...|.
+..+-+-+#|.
+..|.|.|.|.
+.#+-^-+-+.
+.+----+O#.
+#+----+...
+......#..."
+    );
+
+    // Frames grow the path one step at a time behind the guard
+    let frames: Vec<String> = frames(&grid, &trajectory, obstruction, false).collect();
+    assert_eq!(frames.len(), trajectory.steps.len());
+    assert_eq!(frames[2].lines().nth(4), Some("....^..#.."));
+    assert_eq!(frames[2].lines().nth(5), Some("....|....."));
+    assert!(render(&grid, &trajectory, obstruction, true).contains("\x1b[1;31mO\x1b[0m"));
+
+    let mut terminal = Vec::new();
+    play(frames[..2].to_vec(), Duration::ZERO, &mut terminal).unwrap();
+    let terminal = String::from_utf8(terminal).unwrap();
+    assert_eq!(terminal, format!("\x1b[H\x1b[2J{}\n\x1b[H\x1b[2J{}\n", frames[0], frames[1]));
+}
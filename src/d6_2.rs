@@ -1,142 +1,164 @@
-use std::collections::{HashMap, HashSet};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
+use crate::d6_1::parse_map;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
+
+// Where the guard stops when walking from each cell in each direction: the
+// last cell before the next obstacle, or `None` if it walks off the map.
+// Lets a loop check jump from turn to turn instead of stepping cell by cell.
+struct Jumps {
+    width: usize,
+    stops: Vec<Option<Point>>,
 }
 
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
+impl Jumps {
+    fn new(grid: &Grid<char>) -> Self {
+        let mut jumps = Jumps {
+            width: grid.width(),
+            stops: vec![None; grid.width() * grid.height() * 4],
+        };
+        // Fill each direction starting from the side the guard walks towards,
+        // so the cell ahead is always done first
+        let points: Vec<Point> = grid.points().collect();
+        for direction in Direction::ALL {
+            let backwards = matches!(direction, Direction::Right | Direction::Down);
+            let order: Box<dyn Iterator<Item = &Point>> =
+                if backwards { Box::new(points.iter().rev()) } else { Box::new(points.iter()) };
+            for &pos in order {
+                let next = pos + direction;
+                let stop = match grid.get(next) {
+                    None => None,
+                    Some('#') => Some(pos),
+                    Some(_) => jumps.stop(next, direction),
+                };
+                let i = jumps.index(pos, direction);
+                jumps.stops[i] = stop;
+            }
         }
+        jumps
     }
 
-    fn next_position(&self, pos: (i32, i32)) -> (i32, i32) {
-        let (x, y) = pos;
-        match self {
-            Direction::Up => (x, y - 1),
-            Direction::Right => (x + 1, y),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-        }
+    fn index(&self, pos: Point, direction: Direction) -> usize {
+        (pos.y as usize * self.width + pos.x as usize) * 4 + direction as usize
     }
-}
 
-#[derive(Clone)]
-struct Grid {
-    cells: Vec<Vec<char>>,
-    rows: i32,
-    cols: i32,
-}
+    fn stop(&self, pos: Point, direction: Direction) -> Option<Point> {
+        self.stops[self.index(pos, direction)]
+    }
 
-impl Grid {
-    fn new(input: &str) -> (Self, (i32, i32), Direction) {
-        let mut cells: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-        let rows = cells.len() as i32;
-        let cols = cells[0].len() as i32;
-        let mut start_pos = (0, 0);
-        let mut start_dir = Direction::Up;
-
-        // Find starting position and direction
-        for (y, row) in cells.iter_mut().enumerate() {
-            for (x, cell) in row.iter_mut().enumerate() {
-                if *cell == '^' {
-                    start_pos = (x as i32, y as i32);
-                    start_dir = Direction::Up;
-                    *cell = '.';
-                    break;
-                }
-            }
+    // Like `stop`, but with an extra obstruction at `block`.
+    fn stop_with(&self, pos: Point, direction: Direction, block: Point) -> Option<Point> {
+        let stop = self.stop(pos, direction);
+        let delta = direction.delta();
+        let along = |p: Point| (p.x - pos.x) * delta.x + (p.y - pos.y) * delta.y;
+        let distance = along(block);
+        let ahead = distance > 0 && block == pos + delta * distance;
+        if ahead && stop.is_none_or(|stop| distance <= along(stop)) {
+            Some(block - delta)
+        } else {
+            stop
         }
-
-        (Grid { cells, rows, cols }, start_pos, start_dir)
     }
+}
 
-    fn is_valid_position(&self, (x, y): (i32, i32)) -> bool {
-        x >= 0 && x < self.cols && y >= 0 && y < self.rows
-    }
+// A fixed-size set of (cell, direction) states, cleared between checks.
+struct Bitset(Vec<u64>);
 
-    fn is_obstacle(&self, (x, y): (i32, i32)) -> bool {
-        self.cells[y as usize][x as usize] == '#'
+impl Bitset {
+    fn new(len: usize) -> Self {
+        Bitset(vec![0; len.div_ceil(64)])
     }
 
-    fn add_obstruction(&mut self, (x, y): (i32, i32)) {
-        self.cells[y as usize][x as usize] = '#';
+    /// Adds `i`, returning whether it was new.
+    fn insert(&mut self, i: usize) -> bool {
+        let (word, bit) = (i / 64, 1 << (i % 64));
+        let new = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        new
     }
 
-    fn remove_obstruction(&mut self, (x, y): (i32, i32)) {
-        self.cells[y as usize][x as usize] = '.';
+    fn clear(&mut self) {
+        self.0.fill(0);
     }
 }
 
-fn detect_loop(grid: &Grid, start_pos: (i32, i32), start_dir: Direction) -> bool {
-    let mut pos = start_pos;
-    let mut direction = start_dir;
-    let mut state_history: HashMap<((i32, i32), Direction), usize> = HashMap::new();
-    let mut steps = 0;
-
-    loop {
-        // Record current state
-        let state = (pos, direction);
-
-        // If we've seen this state before, we're in a loop
-        if let Some(previous_step) = state_history.get(&state) {
+// Whether an obstruction at `block` traps the guard. The guard only ever
+// changes state at a turn, so those are the only states worth recording.
+fn detect_loop(jumps: &Jumps, start_pos: Point, start_dir: Direction, block: Point, visited: &mut Bitset) -> bool {
+    visited.clear();
+    let (mut pos, mut direction) = (start_pos, start_dir);
+    while let Some(stop) = jumps.stop_with(pos, direction, block) {
+        if !visited.insert(jumps.index(stop, direction)) {
             return true;
         }
+        pos = stop;
+        direction = direction.turn_right();
+    }
+    false
+}
 
-        state_history.insert(state, steps);
-        steps += 1;
-
-        // Get next position
-        let next_pos = direction.next_position(pos);
-
-        // If out of bounds, not a loop
-        if !grid.is_valid_position(next_pos) {
-            return false;
+// Every cell the guard walks through without any extra obstruction, in the
+// order it first gets there, and whether it ends up walking in a loop.
+fn guard_path(grid: &Grid<char>, start_pos: Point, start_dir: Direction) -> (Vec<Point>, bool) {
+    // The directions the guard has faced on each cell, one bit each
+    let mut seen = Grid::new(grid.width(), grid.height(), 0u8);
+    let (mut pos, mut direction) = (start_pos, start_dir);
+    let mut path = Vec::new();
+    loop {
+        let bit = 1 << direction as usize;
+        if seen[pos] & bit != 0 {
+            return (path, true);
         }
-
-        // Update position/direction based on obstacles
-        if grid.is_obstacle(next_pos) {
-            direction = direction.turn_right();
-        } else {
-            pos = next_pos;
+        if seen[pos] == 0 {
+            path.push(pos);
+        }
+        seen[pos] |= bit;
+        match grid.get(pos + direction) {
+            None => return (path, false),
+            Some('#') => direction = direction.turn_right(),
+            Some(_) => pos += direction,
         }
     }
 }
 
-fn find_loop_positions(input: &str) -> usize {
-    let (mut grid, start_pos, start_dir) = Grid::new(input);
-    let mut loop_positions = HashSet::new();
+fn find_loop_positions(input: &str) -> Result<usize, AocError> {
+    let (grid, start_pos, start_dir) = parse_map(input)?;
+    let jumps = Jumps::new(&grid);
+    let mut visited = Bitset::new(jumps.stops.len());
+
+    // An obstruction off the guard's path is never reached, so it can't
+    // change anything. If the guard already walks in a loop, that means
+    // every such cell keeps it trapped, so all of them have to be tried.
+    let (path, loops) = guard_path(&grid, start_pos, start_dir);
+    let candidates = if loops { grid.points().filter(|&pos| grid[pos] == '.').collect() } else { path };
+    let count = candidates
+        .into_iter()
+        .filter(|&pos| pos != start_pos)
+        .filter(|&pos| detect_loop(&jumps, start_pos, start_dir, pos, &mut visited))
+        .count();
+    Ok(count)
+}
+
+pub struct Solver;
 
-    // Try each empty position
-    for y in 0..grid.rows {
-        for x in 0..grid.cols {
-            let pos = (x, y);
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        6
+    }
 
-            // Skip if not empty or if it's the start position
-            if pos == start_pos || grid.is_obstacle(pos) {
-                continue;
-            }
+    fn part(&self) -> u32 {
+        2
+    }
 
-            // Add obstruction and check for loop
-            grid.add_obstruction(pos);
-            if detect_loop(&grid, start_pos, start_dir) {
-                loop_positions.insert(pos);
-            }
-            grid.remove_obstruction(pos);
-        }
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(find_loop_positions(input)?.into())
     }
 
-    loop_positions.len()
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_map(input).map(drop)
+    }
 }
+
 #[test]
 fn sample() {
     let example_input = "....#.....
@@ -150,13 +172,81 @@ fn sample() {
 #.........
 ......#...";
 
-    let result = find_loop_positions(example_input);
+    let result = find_loop_positions(example_input).unwrap();
     println!("Number of possible loop-causing positions: {}", result);
     assert_eq!(result, 6, "Expected 6 positions, but got {}", result);
 }
+
+// Walks one cell at a time on a map with the obstruction drawn in.
+#[cfg(test)]
+fn loops_slowly(grid: &Grid<char>, mut pos: Point, mut direction: Direction) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    while seen.insert((pos, direction)) {
+        match grid.get(pos + direction) {
+            None => return false,
+            Some('#') => direction = direction.turn_right(),
+            Some(_) => pos += direction,
+        }
+    }
+    true
+}
+
+// Counts the obstructions that trap the guard by trying every empty cell.
+#[cfg(test)]
+fn count_slowly(input: &str) -> usize {
+    let (mut grid, start_pos, start_dir) = parse_map(input).unwrap();
+    let mut count = 0;
+    for pos in grid.points().collect::<Vec<_>>() {
+        if pos != start_pos && grid[pos] == '.' {
+            grid.set(pos, '#');
+            count += loops_slowly(&grid, start_pos, start_dir) as usize;
+            grid.set(pos, '.');
+        }
+    }
+    count
+}
+
 #[test]
-fn main() {
-    let input = include_str!("d6.in");
-    let result = find_loop_positions(input);
-    println!("Number of possible loop-causing positions: {}", result);
+fn matches_step_by_step() {
+    // Small random maps, 15% obstacles, from a fixed seed
+    let mut rng = crate::rng::Rng::new(12345);
+    let maps: Vec<String> = (0..200)
+        .map(|_| {
+            let guard = rng.below(100);
+            (0..100)
+                .map(|i| match i {
+                    _ if i == guard => "^",
+                    _ if rng.below(100) < 15 => "#",
+                    _ => ".",
+                })
+                .collect::<Vec<_>>()
+                .chunks(10)
+                .map(|row| row.concat())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+
+    let mut loops = 0;
+    for map in maps {
+        let expected = count_slowly(&map);
+        assert_eq!(find_loop_positions(&map), Ok(expected), "{}", map);
+        loops += expected;
+    }
+    assert!(loops > 0);
+}
+
+#[test]
+fn already_looping() {
+    // The guard walks in a loop before any obstruction is placed, so every
+    // empty cell off that loop keeps it trapped
+    let map = ".#...
+....#
+.^...
+#....
+...#.";
+    let (grid, start_pos, start_dir) = parse_map(map).unwrap();
+    assert!(guard_path(&grid, start_pos, start_dir).1);
+    assert_eq!(count_slowly(map), 13);
+    assert_eq!(find_loop_positions(map), Ok(13));
 }
@@ -1,4 +1,9 @@
-use std::fs::read_to_string;
+use crate::error::{non_empty_lines, Line};
+use crate::{Answer, AocError, Solution};
+
+// Every operator combination gets tried, and 32 numbers already mean 2^31
+// of them.
+const MAX_NUMBERS: usize = 32;
 
 #[derive(Debug)]
 struct Equation {
@@ -7,36 +12,41 @@ struct Equation {
 }
 
 impl Equation {
-    fn parse(line: &str) -> Option<Self> {
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() != 2 {
-            return None;
-        }
+    fn parse(line: Line) -> Result<Self, AocError> {
+        let (test_value, numbers) = line.split_once(":")?;
 
-        let test_value = parts[0].trim().parse().ok()?;
-        let numbers = parts[1]
+        let test_value = line.parse(test_value)?;
+        let numbers: Vec<i64> = numbers
             .split_whitespace()
-            .filter_map(|n| n.parse().ok())
-            .collect();
+            .map(|n| line.parse(n))
+            .collect::<Result<_, _>>()?;
+        if numbers.is_empty() {
+            return Err(line.error("expected at least one number after the colon"));
+        }
+        if numbers.len() > MAX_NUMBERS {
+            return Err(line.error(format!("expected at most {} numbers, got {}", MAX_NUMBERS, numbers.len())));
+        }
 
-        Some(Equation {
+        Ok(Equation {
             test_value,
             numbers,
         })
     }
 
-    fn evaluate(&self, operators: &[char]) -> i64 {
+    // The value of the numbers joined by `operators`, or `None` if it
+    // doesn't fit an i64 along the way.
+    fn evaluate(&self, operators: &[char]) -> Option<i64> {
         let mut result = self.numbers[0];
         
         for (i, &op) in operators.iter().enumerate() {
             match op {
-                '+' => result += self.numbers[i + 1],
-                '*' => result *= self.numbers[i + 1],
+                '+' => result = result.checked_add(self.numbers[i + 1])?,
+                '*' => result = result.checked_mul(self.numbers[i + 1])?,
                 _ => unreachable!(),
             }
         }
         
-        result
+        Some(result)
     }
 
     fn is_valid(&self) -> bool {
@@ -45,14 +55,14 @@ impl Equation {
         
         // Generate all possible operator combinations
         let mut valid = false;
-        for i in 0..(1 << num_operators) {
+        for i in 0..(1u64 << num_operators) {
             let mut current_ops = Vec::with_capacity(num_operators);
             
             for j in 0..num_operators {
-                current_ops.push(if (i & (1 << j)) == 0 { '+' } else { '*' });
+                current_ops.push(if (i & (1u64 << j)) == 0 { '+' } else { '*' });
             }
             
-            if self.evaluate(&current_ops) == self.test_value {
+            if self.evaluate(&current_ops) == Some(self.test_value) {
                 valid = true;
                 break;
             }
@@ -61,18 +71,34 @@ impl Equation {
         valid
     }
 }
-#[test]
-fn main() {
-    let input = read_to_string("src/d7.in").expect("Failed to read input file");
-    
-    let result: i64 = input
-        .lines()
-        .filter_map(|line| Equation::parse(line))
-        .filter(|eq| eq.is_valid())
-        .map(|eq| eq.test_value)
-        .sum();
-        
-    println!("Total calibration result: {}", result);
+
+fn parse_input(input: &str) -> Result<Vec<Equation>, AocError> {
+    non_empty_lines(input).map(Equation::parse).collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let result = parse_input(input)?
+            .iter()
+            .filter(|eq| eq.is_valid())
+            .try_fold(0i64, |sum, eq| sum.checked_add(eq.test_value))
+            .ok_or_else(|| AocError::InvalidInput("the test values add up to more than an i64".to_string()))?;
+        Ok(result.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -91,9 +117,9 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-        let result: i64 = input
-            .lines()
-            .filter_map(|line| Equation::parse(line))
+        let result: i64 = parse_input(input)
+            .unwrap()
+            .iter()
             .filter(|eq| eq.is_valid())
             .map(|eq| eq.test_value)
             .sum();
@@ -107,7 +133,18 @@ mod tests {
             test_value: 190,
             numbers: vec![10, 19],
         };
-        assert_eq!(eq.evaluate(&['*']), 190);
-        assert_eq!(eq.evaluate(&['+']), 29);
+        assert_eq!(eq.evaluate(&['*']), Some(190));
+        assert_eq!(eq.evaluate(&['+']), Some(29));
+
+        let eq = Equation {
+            test_value: 0,
+            numbers: vec![i64::MAX, 2],
+        };
+        assert_eq!(eq.evaluate(&['*']), None);
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_too_many_numbers() {
+        let input = format!("1: {}", vec!["1"; 40].join(" "));
+        assert_eq!(Solver.solve(&input), Err(AocError::parse(1, "expected at most 32 numbers, got 40")));
+    }
+}
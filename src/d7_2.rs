@@ -1,4 +1,9 @@
-use std::fs::read_to_string;
+use crate::error::{non_empty_lines, Line};
+use crate::{Answer, AocError, Solution};
+
+// Every operator combination gets tried, and 21 numbers already mean 3^20
+// of them, about as many as a u32 holds.
+const MAX_NUMBERS: usize = 21;
 
 #[derive(Debug)]
 struct Equation {
@@ -7,42 +12,47 @@ struct Equation {
 }
 
 impl Equation {
-    fn parse(line: &str) -> Option<Self> {
-        let parts: Vec<&str> = line.split(':').collect();
-        if parts.len() != 2 {
-            return None;
-        }
+    fn parse(line: Line) -> Result<Self, AocError> {
+        let (test_value, numbers) = line.split_once(":")?;
 
-        let test_value = parts[0].trim().parse().ok()?;
-        let numbers = parts[1]
+        let test_value = line.parse(test_value)?;
+        let numbers: Vec<i64> = numbers
             .split_whitespace()
-            .filter_map(|n| n.parse().ok())
-            .collect();
+            .map(|n| line.parse(n))
+            .collect::<Result<_, _>>()?;
+        if numbers.is_empty() {
+            return Err(line.error("expected at least one number after the colon"));
+        }
+        if numbers.len() > MAX_NUMBERS {
+            return Err(line.error(format!("expected at most {} numbers, got {}", MAX_NUMBERS, numbers.len())));
+        }
 
-        Some(Equation {
+        Ok(Equation {
             test_value,
             numbers,
         })
     }
 
-    fn evaluate(&self, operators: &[char]) -> i64 {
+    // The value of the numbers joined by `operators`, or `None` if it
+    // doesn't fit an i64 along the way.
+    fn evaluate(&self, operators: &[char]) -> Option<i64> {
         let mut result = self.numbers[0];
         
         for (i, &op) in operators.iter().enumerate() {
             match op {
-                '+' => result += self.numbers[i + 1],
-                '*' => result *= self.numbers[i + 1],
+                '+' => result = result.checked_add(self.numbers[i + 1])?,
+                '*' => result = result.checked_mul(self.numbers[i + 1])?,
                 '|' => {
                     // Concatenation operator (||)
                     result = format!("{}{}", result, self.numbers[i + 1])
                         .parse()
-                        .unwrap_or(0);
+                        .ok()?;
                 }
                 _ => unreachable!(),
             }
         }
         
-        result
+        Some(result)
     }
 
     fn is_valid(&self) -> bool {
@@ -66,7 +76,7 @@ impl Equation {
                 n /= 3;
             }
             
-            if self.evaluate(&current_ops) == self.test_value {
+            if self.evaluate(&current_ops) == Some(self.test_value) {
                 valid = true;
                 break;
             }
@@ -75,18 +85,34 @@ impl Equation {
         valid
     }
 }
-#[test]
-fn main() {
-    let input = read_to_string("src/d7.in").expect("Failed to read input file");
-    
-    let result: i64 = input
-        .lines()
-        .filter_map(|line| Equation::parse(line))
-        .filter(|eq| eq.is_valid())
-        .map(|eq| eq.test_value)
-        .sum();
-        
-    println!("Total calibration result: {}", result);
+
+fn parse_input(input: &str) -> Result<Vec<Equation>, AocError> {
+    non_empty_lines(input).map(Equation::parse).collect()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        let result = parse_input(input)?
+            .iter()
+            .filter(|eq| eq.is_valid())
+            .try_fold(0i64, |sum, eq| sum.checked_add(eq.test_value))
+            .ok_or_else(|| AocError::InvalidInput("the test values add up to more than an i64".to_string()))?;
+        Ok(result.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -105,9 +131,9 @@ mod tests {
 21037: 9 7 18 13
 292: 11 6 16 20";
 
-        let result: i64 = input
-            .lines()
-            .filter_map(|line| Equation::parse(line))
+        let result: i64 = parse_input(input)
+            .unwrap()
+            .iter()
             .filter(|eq| eq.is_valid())
             .map(|eq| eq.test_value)
             .sum();
@@ -121,7 +147,7 @@ mod tests {
             test_value: 156,
             numbers: vec![15, 6],
         };
-        assert_eq!(eq.evaluate(&['|']), 156);
+        assert_eq!(eq.evaluate(&['|']), Some(156));
     }
 
     #[test]
@@ -130,12 +156,12 @@ mod tests {
             test_value: 192,
             numbers: vec![17, 8, 14],
         };
-        assert_eq!(eq.evaluate(&['|', '+']), 192); // 17 || 8 + 14
+        assert_eq!(eq.evaluate(&['|', '+']), Some(192)); // 17 || 8 + 14
         
         let eq2 = Equation {
             test_value: 7290,
             numbers: vec![6, 8, 6, 15],
         };
-        assert_eq!(eq2.evaluate(&['*', '|', '*']), 7290); // 6 * 8 || 6 * 15
+        assert_eq!(eq2.evaluate(&['*', '|', '*']), Some(7290)); // 6 * 8 || 6 * 15
     }
-}
\ No newline at end of file
+}
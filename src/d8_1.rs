@@ -1,22 +1,13 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-
-    // Calculate squared distance between two points
-    fn distance_squared(&self, other: &Point) -> i32 {
-        let dx = self.x - other.x;
-        let dy = self.y - other.y;
-        dx * dx + dy * dy
-    }
+// Calculate squared distance between two points
+fn distance_squared(a: &Point, b: &Point) -> i64 {
+    let dx = a.x - b.x;
+    let dy = a.y - b.y;
+    dx * dx + dy * dy
 }
 
 // Check if point is collinear with two antennas
@@ -41,7 +32,7 @@ fn is_between(p1: &Point, p2: &Point, p3: &Point) -> bool {
     }
 }
 
-fn find_antinodes(a1: &Point, a2: &Point, bounds: (i32, i32)) -> Vec<Point> {
+fn find_antinodes(a1: &Point, a2: &Point, grid: &Grid<char>) -> Vec<Point> {
     let mut antinodes = Vec::new();
     
     // Ensure we're not checking the same point
@@ -50,54 +41,48 @@ fn find_antinodes(a1: &Point, a2: &Point, bounds: (i32, i32)) -> Vec<Point> {
     }
 
     // For each point in bounds
-    for x in 0..=bounds.0 {
-        for y in 0..=bounds.1 {
-            let p = Point::new(x, y);
-            
-            // Skip if point is an antenna location
-            if p == *a1 || p == *a2 {
-                continue;
-            }
+    for p in grid.points() {
+        // Skip if point is an antenna location
+        if p == *a1 || p == *a2 {
+            continue;
+        }
 
-            // Check if point is collinear with antennas
-            if !is_collinear(a1, a2, &p) {
-                continue;
-            }
+        // Check if point is collinear with antennas
+        if !is_collinear(a1, a2, &p) {
+            continue;
+        }
 
-            // Calculate distances
-            let d1 = p.distance_squared(a1);
-            let d2 = p.distance_squared(a2);
+        // Calculate distances
+        let d1 = distance_squared(&p, a1);
+        let d2 = distance_squared(&p, a2);
 
-            // Check for exact 1:2 ratio and that point isn't between antennas
-            if (d1 == 4 * d2 || d2 == 4 * d1) && !is_between(a1, a2, &p) {
-                antinodes.push(p);
-            }
+        // Check for exact 1:2 ratio and that point isn't between antennas
+        if (d1 == 4 * d2 || d2 == 4 * d1) && !is_between(a1, a2, &p) {
+            antinodes.push(p);
         }
     }
 
     antinodes
 }
 
-fn parse_map(input: &str) -> (HashMap<char, Vec<Point>>, (i32, i32)) {
-    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
+// Antenna positions grouped by frequency.
+type Antennas = HashMap<char, Vec<Point>>;
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            if ch != '.' {
-                antennas.entry(ch).or_default().push(Point::new(x as i32, y as i32));
-            }
-            max_x = max_x.max(x as i32);
+fn parse_map(input: &str) -> Result<(Antennas, Grid<char>), AocError> {
+    let grid = Grid::parse(input)?;
+    let mut antennas = Antennas::new();
+
+    for (p, &ch) in grid.iter() {
+        if ch != '.' {
+            antennas.entry(ch).or_default().push(p);
         }
-        max_y = max_y.max(y as i32);
     }
 
-    (antennas, (max_x, max_y))
+    Ok((antennas, grid))
 }
 
-fn count_antinodes(input: &str) -> usize {
-    let (antennas, bounds) = parse_map(input);
+fn count_antinodes(input: &str) -> Result<usize, AocError> {
+    let (antennas, grid) = parse_map(input)?;
     let mut all_antinodes = HashSet::new();
 
     // Process each frequency
@@ -110,20 +95,33 @@ fn count_antinodes(input: &str) -> usize {
         // Check each pair of antennas
         for i in 0..positions.len() {
             for j in (i + 1)..positions.len() {
-                let nodes = find_antinodes(&positions[i], &positions[j], bounds);
+                let nodes = find_antinodes(&positions[i], &positions[j], &grid);
                 all_antinodes.extend(nodes);
             }
         }
     }
 
-    all_antinodes.len()
+    Ok(all_antinodes.len())
 }
-#[test]
-fn main() {
-    let input = include_str!("d8.in");
 
-    let result = count_antinodes(input);
-    println!("Number of unique antinode locations: {}", result);
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(count_antinodes(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_map(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -144,7 +142,7 @@ mod tests {
 .........A..
 ............
 ............";
-        assert_eq!(count_antinodes(input), 14);
+        assert_eq!(count_antinodes(input), Ok(14));
     }
 
     #[test]
@@ -159,6 +157,6 @@ mod tests {
 ......#...
 ..........
 ..........";
-        assert_eq!(count_antinodes(input), 2);
+        assert_eq!(count_antinodes(input), Ok(2));
     }
-}
\ No newline at end of file
+}
@@ -1,19 +1,10 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{Answer, AocError, Solution};
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-}
-
 // Calculate Greatest Common Divisor using Euclidean algorithm
-fn gcd(mut a: i32, mut b: i32) -> i32 {
+fn gcd(mut a: i64, mut b: i64) -> i64 {
     while b != 0 {
         let temp = b;
         b = a % b;
@@ -33,7 +24,7 @@ fn is_collinear(p1: &Point, p2: &Point, test_point: &Point) -> bool {
     dx1 * dy2 == dx2 * dy1
 }
 
-fn find_antinodes(antennas: &[Point], bounds: (i32, i32)) -> HashSet<Point> {
+fn find_antinodes(antennas: &[Point], grid: &Grid<char>) -> HashSet<Point> {
     let mut antinodes = HashSet::new();
     
     // Add all antenna positions if there are multiple antennas of this frequency
@@ -42,16 +33,12 @@ fn find_antinodes(antennas: &[Point], bounds: (i32, i32)) -> HashSet<Point> {
     }
     
     // Check all points for being collinear with any pair of antennas
-    for x in 0..=bounds.0 {
-        for y in 0..=bounds.1 {
-            let point = Point::new(x, y);
-            
-            // For each pair of antennas
-            for i in 0..antennas.len() {
-                for j in (i + 1)..antennas.len() {
-                    if is_collinear(&antennas[i], &antennas[j], &point) {
-                        antinodes.insert(point);
-                    }
+    for point in grid.points() {
+        // For each pair of antennas
+        for i in 0..antennas.len() {
+            for j in (i + 1)..antennas.len() {
+                if is_collinear(&antennas[i], &antennas[j], &point) {
+                    antinodes.insert(point);
                 }
             }
         }
@@ -60,45 +47,55 @@ fn find_antinodes(antennas: &[Point], bounds: (i32, i32)) -> HashSet<Point> {
     antinodes
 }
 
-fn parse_map(input: &str) -> (HashMap<char, Vec<Point>>, (i32, i32)) {
-    let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
+// Antenna positions grouped by frequency.
+type Antennas = HashMap<char, Vec<Point>>;
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            if ch != '.' && ch != '#' {  // Ignore existing antinode markers
-                antennas.entry(ch).or_default().push(Point::new(x as i32, y as i32));
-            }
-            max_x = max_x.max(x as i32);
+fn parse_map(input: &str) -> Result<(Antennas, Grid<char>), AocError> {
+    let grid = Grid::parse(input)?;
+    let mut antennas = Antennas::new();
+
+    for (p, &ch) in grid.iter() {
+        if ch != '.' && ch != '#' {  // Ignore existing antinode markers
+            antennas.entry(ch).or_default().push(p);
         }
-        max_y = max_y.max(y as i32);
     }
 
-    (antennas, (max_x, max_y))
+    Ok((antennas, grid))
 }
 
-fn count_antinodes(input: &str) -> usize {
-    let (antennas, bounds) = parse_map(input);
+fn count_antinodes(input: &str) -> Result<usize, AocError> {
+    let (antennas, grid) = parse_map(input)?;
     let mut all_antinodes = HashSet::new();
 
     // Process each frequency separately
     for (_, freq_antennas) in antennas {
         if freq_antennas.len() >= 2 {
-            let freq_antinodes = find_antinodes(&freq_antennas, bounds);
+            let freq_antinodes = find_antinodes(&freq_antennas, &grid);
             all_antinodes.extend(freq_antinodes);
         }
     }
 
-    all_antinodes.len()
+    Ok(all_antinodes.len())
 }
 
-#[test]
-fn main(){
-    let input = include_str!("d8.in");
+pub struct Solver;
 
-    let result = count_antinodes(input);
-    println!("Number of unique antinode locations: {}", result);
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(count_antinodes(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_map(input).map(drop)
+    }
 }
 
 #[cfg(test)]
@@ -119,7 +116,7 @@ mod tests {
 .........A..
 ............
 ............";
-        assert_eq!(count_antinodes(input), 34);
+        assert_eq!(count_antinodes(input), Ok(34));
     }
 
     #[test]
@@ -134,7 +131,7 @@ mod tests {
 ..........
 ....#.....
 ..........";
-        assert_eq!(count_antinodes(input), 9);
+        assert_eq!(count_antinodes(input), Ok(9));
     }
 
     #[test]
@@ -144,6 +141,6 @@ mod tests {
 .....
 ..a..
 .....";
-        assert_eq!(count_antinodes(input), 5);  // All points in the middle column
+        assert_eq!(count_antinodes(input), Ok(5));  // All points in the middle column
     }
-}
\ No newline at end of file
+}
@@ -1,64 +1,166 @@
-fn create_block_array(input: &str) -> Vec<Option<usize>> {
-    let mut result = Vec::new();
-    let mut file_id = 0;
+use crate::{Answer, AocError, Solution};
 
-    for (i, c) in input.chars().enumerate() {
-        if !c.is_numeric() {
-            continue;
-        }
-        let length = c.to_digit(10).expect("fail") as usize;
-        for _ in 0..length {
-            result.push(if i % 2 == 0 { Some(file_id) } else { None });
-        }
+/// A run of consecutive blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Span {
+    pub(crate) start: usize,
+    pub(crate) len: usize,
+}
+
+/// The disk as runs rather than blocks: every file by id, and the free
+/// spans between them in disk order. Empty free spans are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Disk {
+    pub(crate) files: Vec<Span>,
+    pub(crate) free: Vec<Span>,
+}
+
+pub(crate) fn parse_disk(input: &str) -> Result<Disk, AocError> {
+    let mut disk = Disk {
+        files: Vec::new(),
+        free: Vec::new(),
+    };
+    let mut start = 0;
+
+    // The whole disk map is a single line of digits
+    for (i, c) in input.trim_end().chars().enumerate() {
+        let Some(len) = c.to_digit(10) else {
+            return Err(AocError::parse_at(1, i + 1, format!("expected a digit, got {:?}", c)));
+        };
+        let span = Span {
+            start,
+            len: len as usize,
+        };
         if i % 2 == 0 {
-            file_id += 1;
+            disk.files.push(span);
+        } else if span.len > 0 {
+            disk.free.push(span);
         }
+        start += span.len;
     }
-    result
+    Ok(disk)
+}
+
+/// Sum of block position times file id over `(file id, span)` pieces.
+pub(crate) fn checksum(pieces: impl IntoIterator<Item = (usize, Span)>) -> u64 {
+    pieces
+        .into_iter()
+        .map(|(id, span)| {
+            // start + (start + 1) + ... + (start + len - 1)
+            let (start, len) = (span.start as u64, span.len as u64);
+            id as u64 * (start * len + len * len.saturating_sub(1) / 2)
+        })
+        .sum()
 }
 
-fn compact_disk(mut blocks: Vec<Option<usize>>) -> Vec<Option<usize>> {
-    let mut changed = true;
-    while changed {
-        changed = false;
-        if let Some(right_idx) = blocks.iter().rposition(|b| b.is_some()) {
-            if let Some(left_idx) = blocks[..right_idx].iter().position(|b| b.is_none()) {
-                blocks[left_idx] = blocks[right_idx];
-                blocks[right_idx] = None;
-                changed = true;
+// Moves blocks one at a time from the end of the disk into the first free
+// block, returning where each file's blocks end up. Free spans are filled
+// left to right and files emptied right to left, so each is visited once.
+fn compact_blocks(disk: &Disk) -> Vec<(usize, Span)> {
+    let mut files = disk.files.clone();
+    let mut pieces = Vec::new();
+    // Files past `last` have been moved completely
+    let mut last = files.len();
+
+    'free: for &free in &disk.free {
+        let mut free = free;
+        while free.len > 0 {
+            while last > 0 && files[last - 1].len == 0 {
+                last -= 1;
+            }
+            if last == 0 || files[last - 1].start < free.start {
+                break 'free;
             }
+            // A file keeps its first blocks and gives up the ones at its end
+            let file = &mut files[last - 1];
+            let moved = free.len.min(file.len);
+            pieces.push((
+                last - 1,
+                Span {
+                    start: free.start,
+                    len: moved,
+                },
+            ));
+            file.len -= moved;
+            free.start += moved;
+            free.len -= moved;
         }
     }
-    blocks
-}
 
-fn calculate_checksum(blocks: &[Option<usize>]) -> u64 {
-    blocks
-        .iter()
-        .enumerate()
-        .filter_map(|(pos, &file_id)| file_id.map(|id| (pos as u64) * (id as u64)))
-        .sum()
+    pieces.extend(files.into_iter().enumerate().filter(|(_, span)| span.len > 0));
+    pieces
 }
 
-fn solve(input: &str) -> u64 {
-    let blocks = create_block_array(input);
-    let compacted = compact_disk(blocks);
-    calculate_checksum(&compacted)
+fn solve(input: &str) -> Result<u64, AocError> {
+    let disk = parse_disk(input)?;
+    Ok(checksum(compact_blocks(&disk)))
 }
-#[test]
-fn main() {
-    let input = include_str!("d9.in");
-    let result = solve(input);
-    println!("Filesystem checksum: {}", result);
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_disk(input).map(drop)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rng::Rng;
 
     #[test]
     fn test_example() {
         let input = "2333133121414131402";
-        assert_eq!(solve(input), 1928);
+        assert_eq!(solve(input), Ok(1928));
+        assert_eq!(solve("12345"), Ok(60));
+        assert!(solve("12a45").is_err());
+    }
+
+    #[test]
+    fn test_matches_block_by_block() {
+        // Compacts an explicit block array, one block at a time
+        fn compact_slowly(disk: &Disk) -> u64 {
+            let size = disk.files.iter().chain(&disk.free).map(|s| s.start + s.len).max().unwrap_or(0);
+            let mut blocks = vec![None; size];
+            for (id, file) in disk.files.iter().enumerate() {
+                blocks[file.start..file.start + file.len].fill(Some(id));
+            }
+            let (mut left, mut right) = (0, size);
+            loop {
+                while left < size && blocks[left].is_some() {
+                    left += 1;
+                }
+                while right > 0 && blocks[right - 1].is_none() {
+                    right -= 1;
+                }
+                if left >= right {
+                    break;
+                }
+                blocks.swap(left, right - 1);
+            }
+            blocks.iter().enumerate().map(|(i, id)| (i * id.unwrap_or(0)) as u64).sum()
+        }
+
+        let mut rng = Rng::new(99);
+        for _ in 0..200 {
+            let digits: String = (0..25)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            let disk = parse_disk(&digits).unwrap();
+            assert_eq!(checksum(compact_blocks(&disk)), compact_slowly(&disk), "{}", digits);
+        }
     }
 }
@@ -1,118 +1,135 @@
-#[derive(Debug, Clone)]
-struct File {
-    id: usize,
-    size: usize,
-    start_pos: usize,
-}
+use crate::d9_1::{checksum, parse_disk, Disk, Span};
+use crate::{Answer, AocError, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 
-fn create_initial_state(input: &str) -> (Vec<Option<usize>>, Vec<File>) {
-    let mut blocks = Vec::new();
-    let mut files = Vec::new();
-    let mut current_pos = 0;
-    let mut file_id = 0;
+// Disk map digits are single digits, so no file is longer than this.
+const MAX_SPAN: usize = 9;
 
-    for (i, c) in input.chars().enumerate() {
-        if !c.is_numeric() {
-            continue;
-        }
-        let length = c.to_digit(10).unwrap() as usize;
-        if i % 2 == 0 {
-            // File block
-            files.push(File {
-                id: file_id,
-                size: length,
-                start_pos: current_pos,
-            });
-            for _ in 0..length {
-                blocks.push(Some(file_id));
-            }
-            file_id += 1;
-        } else {
-            // Free space
-            for _ in 0..length {
-                blocks.push(None);
-            }
+// Moves each file once, highest id first, into the leftmost free span that
+// fits it. Free spans are kept in one min-heap of (start, length) per span
+// size, so the leftmost fit is the smallest top across the heaps for sizes
+// the file fits in. Spans longer than any file share the last heap. Space
+// freed by a moved file is never reused, because every file still to move
+// starts left of it.
+fn compact_files(disk: &Disk) -> Vec<(usize, Span)> {
+    let mut heaps: Vec<BinaryHeap<Reverse<(usize, usize)>>> = vec![BinaryHeap::new(); MAX_SPAN + 1];
+    let mut push = |heaps: &mut Vec<BinaryHeap<_>>, start: usize, len: usize| {
+        heaps[len.min(MAX_SPAN)].push(Reverse((start, len)));
+    };
+
+    // An empty file leaves the free space on either side of it as one gap
+    let mut gaps: Vec<Span> = Vec::new();
+    for free in &disk.free {
+        match gaps.last_mut() {
+            Some(gap) if gap.start + gap.len == free.start => gap.len += free.len,
+            _ => gaps.push(*free),
         }
-        current_pos += length;
     }
-    (blocks, files)
-}
+    for gap in gaps {
+        push(&mut heaps, gap.start, gap.len);
+    }
 
-fn find_leftmost_fit(blocks: &[Option<usize>], file_size: usize) -> Option<usize> {
-    let mut current_free = 0;
-    let mut start_pos = None;
+    let mut files = disk.files.clone();
+    for file in files.iter_mut().rev().filter(|file| file.len > 0) {
+        let fit = (file.len..=MAX_SPAN)
+            .filter_map(|size| heaps[size].peek().map(|&Reverse((start, len))| (start, len, size)))
+            .min();
+        let Some((start, len, size)) = fit.filter(|&(start, _, _)| start < file.start) else {
+            continue;
+        };
 
-    for (i, block) in blocks.iter().enumerate() {
-        if block.is_none() {
-            if start_pos.is_none() {
-                start_pos = Some(i);
-            }
-            current_free += 1;
-            if current_free >= file_size {
-                return start_pos;
-            }
-        } else {
-            current_free = 0;
-            start_pos = None;
+        heaps[size].pop();
+        if len > file.len {
+            push(&mut heaps, start + file.len, len - file.len);
         }
+        file.start = start;
     }
-    None
+    files.into_iter().enumerate().collect()
 }
 
-fn move_file(blocks: &mut Vec<Option<usize>>, file: &File, new_pos: usize) {
-    // Clear old position
-    for i in file.start_pos..file.start_pos + file.size {
-        blocks[i] = None;
-    }
-    // Set new position
-    for i in new_pos..new_pos + file.size {
-        blocks[i] = Some(file.id);
-    }
+fn solve(input: &str) -> Result<u64, AocError> {
+    let disk = parse_disk(input)?;
+    Ok(checksum(compact_files(&disk)))
 }
 
-fn compact_disk(mut blocks: Vec<Option<usize>>, mut files: Vec<File>) -> Vec<Option<usize>> {
-    // Sort files by ID in descending order
-    files.sort_by_key(|f| std::cmp::Reverse(f.id));
+pub struct Solver;
 
-    for file in files {
-        // Find the leftmost span of free space that can fit this file
-        if let Some(new_pos) = find_leftmost_fit(&blocks, file.size) {
-            // Only move if the new position is to the left of the current position
-            if new_pos < file.start_pos {
-                move_file(&mut blocks, &file, new_pos);
-            }
-        }
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        9
     }
-    blocks
-}
 
-fn calculate_checksum(blocks: &[Option<usize>]) -> u64 {
-    blocks
-        .iter()
-        .enumerate()
-        .filter_map(|(pos, &file_id)| file_id.map(|id| (pos as u64) * (id as u64)))
-        .sum()
-}
+    fn part(&self) -> u32 {
+        2
+    }
 
-fn solve(input: &str) -> u64 {
-    let (blocks, files) = create_initial_state(input);
-    let compacted = compact_disk(blocks, files);
-    calculate_checksum(&compacted)
-}
-#[test]
-fn main() {
-    let input = include_str!("d9.in");
-    let result = solve(input);
-    println!("Filesystem checksum: {}", result);
+    fn solve(&self, input: &str) -> Result<Answer, AocError> {
+        Ok(solve(input)?.into())
+    }
+
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        parse_disk(input).map(drop)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rng::Rng;
 
     #[test]
     fn test_example() {
         let input = "2333133121414131402";
-        assert_eq!(solve(input), 2858);
+        assert_eq!(solve(input), Ok(2858));
+    }
+
+    #[test]
+    fn test_split_span_stays_leftmost() {
+        // File 2 takes the front of the 3-block gap, and file 1 then fits
+        // into what is left of it rather than moving past it
+        let disk = parse_disk("13111").unwrap();
+        assert_eq!(
+            compact_files(&disk),
+            [
+                (0, Span { start: 0, len: 1 }),
+                (1, Span { start: 2, len: 1 }),
+                (2, Span { start: 1, len: 1 }),
+            ]
+        );
+        // An empty file takes up no space, so it doesn't use up the gap
+        assert_eq!(solve("12011"), Ok(2));
+        // and the free space on both sides of it is one gap: file 2 fits
+        // the two blocks around empty file 1
+        assert_eq!(solve("11012"), Ok(6));
+    }
+    #[test]
+    fn test_matches_block_by_block() {
+        // Moves whole files on an explicit block array, scanning from the
+        // left for the first gap that fits
+        fn compact_slowly(disk: &Disk) -> u64 {
+            let size = disk.files.iter().chain(&disk.free).map(|s| s.start + s.len).max().unwrap_or(0);
+            let mut blocks = vec![None; size];
+            for (id, file) in disk.files.iter().enumerate() {
+                blocks[file.start..file.start + file.len].fill(Some(id));
+            }
+            for (id, file) in disk.files.iter().enumerate().rev().filter(|(_, file)| file.len > 0) {
+                let gap = (0..file.start).find(|&start| blocks[start..start + file.len].iter().all(Option::is_none));
+                if let Some(start) = gap {
+                    blocks[file.start..file.start + file.len].fill(None);
+                    blocks[start..start + file.len].fill(Some(id));
+                }
+            }
+            blocks.iter().enumerate().map(|(i, id)| (i * id.unwrap_or(0)) as u64).sum()
+        }
+
+        let mut rng = Rng::new(99);
+        for _ in 0..200 {
+            let digits: String = (0..25)
+                .map(|_| char::from(b'0' + rng.below(10) as u8))
+                .collect();
+            let disk = parse_disk(&digits).unwrap();
+            assert_eq!(checksum(compact_files(&disk)), compact_slowly(&disk), "{}", digits);
+        }
     }
 }
@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Why a solver could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    /// Part of the input could not be parsed. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: Option<usize>,
+        message: String,
+    },
+    /// The input as a whole is malformed, e.g. a section is missing.
+    InvalidInput(String),
+    /// The input parsed but put a machine into a state it cannot handle.
+    InvalidState(String),
+    /// The input was read fine but the puzzle has no answer for it.
+    NoSolution,
+}
+
+impl AocError {
+    pub fn parse(line: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn parse_at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+
+    /// Shifts a parse error by `lines`, for errors raised while parsing a
+    /// section that doesn't start on the first line of the input.
+    pub fn offset_lines(self, lines: usize) -> Self {
+        match self {
+            AocError::Parse { line, column, message } => AocError::Parse {
+                line: line + lines,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                line,
+                column: Some(column),
+                message,
+            } => write!(f, "line {}, column {}: {}", line, column, message),
+            AocError::Parse { line, message, .. } => write!(f, "line {}: {}", line, message),
+            AocError::InvalidInput(msg) => write!(f, "invalid input: {}", msg),
+            AocError::InvalidState(msg) => write!(f, "{}", msg),
+            AocError::NoSolution => write!(f, "no solution"),
+        }
+    }
+}
+
+impl Error for AocError {}
+
+/// One line of puzzle input, remembering where it came from so parse errors
+/// can point at it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// 1-based line number within the input.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, message: impl Into<String>) -> AocError {
+        AocError::parse(self.number, message)
+    }
+
+    /// An error pointing at `token`, which must be a slice of this line for
+    /// the column to be reported.
+    pub fn error_at(&self, token: &str, message: impl Into<String>) -> AocError {
+        match self.column_of(token) {
+            Some(column) => AocError::parse_at(self.number, column, message),
+            None => self.error(message),
+        }
+    }
+
+    /// Parses `token`, a slice of this line, as a number.
+    pub fn parse<T: FromStr>(&self, token: &str) -> Result<T, AocError> {
+        let trimmed = token.trim();
+        trimmed
+            .parse()
+            .map_err(|_| self.error_at(trimmed, format!("invalid number {:?}", trimmed)))
+    }
+
+    /// Splits the line once around `delimiter`.
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), AocError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(format!("expected {:?} in {:?}", delimiter, self.text)))
+    }
+
+    fn column_of(&self, token: &str) -> Option<usize> {
+        let offset = (token.as_ptr() as usize).checked_sub(self.text.as_ptr() as usize)?;
+        (offset + token.len() <= self.text.len()).then_some(offset + 1)
+    }
+}
+
+/// Every line of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line { number: i + 1, text })
+}
+
+/// The non-blank lines of `input`, numbered from 1.
+pub fn non_empty_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    lines(input).filter(|line| !line.text.trim().is_empty())
+}
+
+/// Groups of consecutive non-blank lines, keeping their original numbers.
+pub fn sections(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut sections = vec![Vec::new()];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if sections.last().is_some_and(|s| !s.is_empty()) {
+                sections.push(Vec::new());
+            }
+        } else if let Some(section) = sections.last_mut() {
+            section.push(line);
+        }
+    }
+    sections.retain(|section| !section.is_empty());
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AocError::parse(5, "invalid opcode 9").to_string(), "line 5: invalid opcode 9");
+        assert_eq!(AocError::parse_at(2, 7, "oops").to_string(), "line 2, column 7: oops");
+        assert_eq!(AocError::parse(1, "x").offset_lines(3), AocError::parse(4, "x"));
+        assert_eq!(AocError::NoSolution.to_string(), "no solution");
+    }
+
+    #[test]
+    fn test_line_parse() {
+        let line = lines("1,2\n3,x").nth(1).unwrap();
+        let (a, b) = line.split_once(",").unwrap();
+        assert_eq!(line.parse::<u32>(a), Ok(3));
+        assert_eq!(line.parse::<u32>(b), Err(AocError::parse_at(2, 3, "invalid number \"x\"")));
+        assert!(line.split_once("-").is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let sections = sections("a\nb\n\n\nc\n");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1][0], Line { number: 5, text: "c" });
+    }
+}
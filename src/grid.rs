@@ -0,0 +1,267 @@
+pub use crate::geometry::Point;
+use crate::error::lines;
+use crate::AocError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the four orthogonal neighbours: up, right, down, left.
+pub const ORTHOGONAL: [Point; 4] = [
+    Point::new(0, -1),
+    Point::new(1, 0),
+    Point::new(0, 1),
+    Point::new(-1, 0),
+];
+
+/// Offsets of all eight neighbours, clockwise from up.
+pub const ALL_DIRECTIONS: [Point; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid<char> {
+    /// Parses one row per non-empty line, one cell per character.
+    pub fn parse(text: &str) -> Result<Self, AocError> {
+        Self::parse_with(text, |c| c)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per non-empty line, converting each character with `f`.
+    /// Every row must be the same length.
+    pub fn parse_with(text: &str, mut f: impl FnMut(char) -> T) -> Result<Self, AocError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for line in lines(text).filter(|line| !line.text.trim_end().is_empty()) {
+            let before = cells.len();
+            cells.extend(line.text.trim_end().chars().map(&mut f));
+            let row_width = cells.len() - before;
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                return Err(line.error(format!("row has {} cells, expected {}", row_width, width)));
+            }
+            height += 1;
+        }
+
+        Ok(Grid { cells, width, height })
+    }
+
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as usize) < self.width && (p.y as usize) < self.height
+    }
+
+    fn offset_of(&self, p: Point) -> Option<usize> {
+        self.contains(p).then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset_of(p).map(move |i| &mut self.cells[i])
+    }
+
+    /// Overwrites the cell at `p`; points outside the grid are ignored.
+    pub fn set(&mut self, p: Point, value: T) {
+        if let Some(cell) = self.get_mut(p) {
+            *cell = value;
+        }
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Point::new((i % width) as i64, (i / width) as i64))
+    }
+
+    /// Every cell with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// In-bounds orthogonal neighbours of `p`.
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(p, &ORTHOGONAL)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours of `p`.
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.offsets(p, &ALL_DIRECTIONS)
+    }
+
+    fn offsets<'a>(&'a self, p: Point, deltas: &'static [Point]) -> impl Iterator<Item = Point> + 'a {
+        deltas
+            .iter()
+            .map(move |&delta| p + delta)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// The first point, row by row, whose cell equals `value`.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, cell)| *cell == value).map(|(p, _)| p)
+    }
+
+    /// Every point whose cell equals `value`.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter().filter(move |(_, cell)| *cell == value).map(|(p, _)| p)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on zero, and an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `p` is outside the grid; use [`Grid::get`] when it may be.
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", p, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(p) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside the {}x{} grid", p, width, height),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "ab.
+.#c
+";
+
+    #[test]
+    fn test_parse_and_get() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'#'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+    }
+
+    #[test]
+    fn test_get_mut_and_display() {
+        let mut grid = Grid::parse(EXAMPLE).unwrap();
+        *grid.get_mut(Point::new(2, 0)).unwrap() = 'X';
+        grid.set(Point::new(9, 9), 'Y');
+        grid[Point::new(0, 1)] = 'Z';
+        assert_eq!(grid[Point::new(2, 0)], 'X');
+        assert_eq!(grid.to_string(), "abX\nZ#c");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let corner: Vec<Point> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Point::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors4(Point::new(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_find_rows_and_columns() {
+        let grid = Grid::parse(EXAMPLE).unwrap();
+        assert_eq!(grid.find(&'c'), Some(Point::new(2, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(grid.find_all(&'.').count(), 2);
+        assert_eq!(grid.row(1), &['.', '#', 'c']);
+        assert_eq!(grid.column(1).collect::<String>(), "b#");
+    }
+
+    #[test]
+    fn test_parse_with_and_map() {
+        let grid = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.map(|&n| n * 2).to_string(), "24\n68");
+    }
+
+    #[test]
+    fn test_ragged_grid() {
+        assert_eq!(Grid::parse("ab\nc"), Err(AocError::parse(2, "row has 1 cells, expected 2")));
+    }
+}
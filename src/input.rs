@@ -0,0 +1,84 @@
+use std::env;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the directory that holds `dN.in` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Directory used when `AOC_INPUT_DIR` is not set.
+pub const DEFAULT_INPUT_DIR: &str = "src";
+
+/// Where puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A single file, used whatever the day.
+    File(PathBuf),
+    /// Standard input, read once.
+    Stdin,
+    /// A directory containing one `dN.in` file per day.
+    Dir(PathBuf),
+}
+
+impl InputSource {
+    /// Parses a `--input` argument: `-` means stdin, anything else is a file.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The input directory from `AOC_INPUT_DIR`, falling back to `src`.
+    pub fn from_env() -> Self {
+        let dir = env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+        InputSource::Dir(PathBuf::from(dir))
+    }
+
+    /// Reads the input for `day`.
+    pub fn load(&self, day: u32) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => read_file(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Dir(dir) => read_file(&dir.join(format!("d{}.in", day))),
+        }
+    }
+}
+
+fn read_file(path: &Path) -> io::Result<String> {
+    read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("src/d1.in"),
+            InputSource::File(PathBuf::from("src/d1.in"))
+        );
+    }
+
+    #[test]
+    fn test_dir_is_keyed_by_day() {
+        let dir = env::temp_dir().join(format!("aoc2024-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("d7.in"), "190: 10 19\n").unwrap();
+
+        let source = InputSource::Dir(dir.clone());
+        assert_eq!(source.load(7).unwrap(), "190: 10 19\n");
+        assert!(source.load(8).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,96 +1,97 @@
 #![deny(clippy::all)]
 #![allow(unused_imports)]
 #![allow(unused)]
-mod d1_1;
-mod d1_2;
-mod d2_1;
-mod d2_2;
-#[rustfmt::skip]
-mod d3_1;
-#[rustfmt::skip]
 
-mod d3_2;
-#[rustfmt::skip]
-
-mod d10_1;
-#[rustfmt::skip]
-
-mod d10_2;
-#[rustfmt::skip]
-
-mod d11_1;
-#[rustfmt::skip]
-
-mod d11_2;
-#[rustfmt::skip]
-mod d4_1;
-#[rustfmt::skip]
-mod d4_2;
-#[rustfmt::skip]
-mod d5_1;
-#[rustfmt::skip]
-mod d5_2;
-
-#[rustfmt::skip]
-mod d6_1;
-#[rustfmt::skip]
-mod d6_2;
-#[rustfmt::skip]
-mod d7_1;
-#[rustfmt::skip]
-mod d7_2;
-#[rustfmt::skip]
-mod d8_1;
-#[rustfmt::skip]
-
-mod d8_2;
-#[rustfmt::skip]
-mod d9_1;
-#[rustfmt::skip]
-
-mod d9_2;
-#[rustfmt::skip]
-mod d12_1;
-#[rustfmt::skip]
-
-mod d12_2;
-#[rustfmt::skip]
-mod d13_1;
-#[rustfmt::skip]
-mod d13_2;
-#[rustfmt::skip]
-
-mod d14_1;
-#[rustfmt::skip]
-
-mod d14_2;
-#[rustfmt::skip]
-
-mod d15_1;
-#[rustfmt::skip]
-
-mod d15_2;
-#[rustfmt::skip]
-
-mod d16_1;
-#[rustfmt::skip]
-
-mod d16_2;
-#[rustfmt::skip]
-
-mod d17_1;
-#[rustfmt::skip]
-
-mod d18_1;
-#[rustfmt::skip]
-
-mod d19_1;
-#[rustfmt::skip]
-
-mod d19_2;
-mod d22_1;
-mod d22_2;
-mod d23_1;
-mod d23_2;
-mod d24_1;
-mod d24_2;
+pub mod answers;
+pub mod bench;
+pub mod bigint;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+mod rng;
+pub mod search;
+mod solution;
+pub mod tools;
+pub mod verify;
+
+pub use error::AocError;
+pub use solution::{Answer, Solution};
+
+/// Declares each day module and registers its `Solver` in [`SOLUTIONS`].
+macro_rules! solutions {
+    ($($module:ident),* $(,)?) => {
+        $(mod $module;)*
+
+        /// Every solved day/part, in puzzle order.
+        pub static SOLUTIONS: &[&dyn Solution] = &[$(&$module::Solver),*];
+    };
+}
+
+solutions! {
+    d1_1,
+    d1_2,
+    d2_1,
+    d2_2,
+    d3_1,
+    d3_2,
+    d4_1,
+    d4_2,
+    d5_1,
+    d5_2,
+    d6_1,
+    d6_2,
+    d7_1,
+    d7_2,
+    d8_1,
+    d8_2,
+    d9_1,
+    d9_2,
+    d10_1,
+    d10_2,
+    d11_1,
+    d11_2,
+    d12_1,
+    d12_2,
+    d13_1,
+    d13_2,
+    d14_1,
+    d14_2,
+    d15_1,
+    d15_2,
+    d16_1,
+    d16_2,
+    d17_1,
+    d17_2,
+    d18_1,
+    d18_2,
+    d19_1,
+    d19_2,
+    d22_1,
+    d22_2,
+    d23_1,
+    d23_2,
+    d24_1,
+    d24_2,
+}
+
+/// Iterates over every registered solution.
+pub fn solutions() -> impl Iterator<Item = &'static dyn Solution> {
+    SOLUTIONS.iter().copied()
+}
+
+/// Looks up the solution for `day`/`part`.
+pub fn find(day: u32, part: u32) -> Option<&'static dyn Solution> {
+    solutions().find(|s| s.day() == day && s.part() == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_sorted_and_unique() {
+        let keys: Vec<(u32, u32)> = solutions().map(|s| (s.day(), s.part())).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]), "{:?}", keys);
+    }
+}
@@ -0,0 +1,54 @@
+//! A small seeded random number generator, for picking test cases that are
+//! the same on every run.
+
+/// SplitMix64: tiny, fast and good enough to pick test operands.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`. The slight bias towards small numbers doesn't
+    /// matter for picking test cases.
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// `width` random bits, least significant first.
+    pub(crate) fn bits(&mut self, width: usize) -> Vec<bool> {
+        let mut word = 0;
+        (0..width)
+            .map(|i| {
+                if i % 64 == 0 {
+                    word = self.next();
+                }
+                word >> (i % 64) & 1 == 1
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        let first: Vec<u64> = (0..5).map(|_| a.next()).collect();
+        assert_eq!(first, (0..5).map(|_| b.next()).collect::<Vec<_>>());
+        assert_ne!(first, (0..5).map(|_| Rng::new(8).next()).collect::<Vec<_>>());
+
+        assert!((0..1000).all(|_| a.below(10) < 10));
+        assert_eq!(a.bits(70).len(), 70);
+    }
+}
@@ -0,0 +1,276 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A cheapest route from the start to a goal, both ends included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+/// Every cheapest route from the start to any goal.
+#[derive(Debug, Clone)]
+pub struct AllPaths<N> {
+    pub cost: u64,
+    /// One of the cheapest routes, both ends included.
+    pub path: Vec<N>,
+    /// Every goal reached at `cost`.
+    pub goals: Vec<N>,
+    /// For each node reached, every neighbour it can be reached from at its
+    /// lowest cost. The start has no entry.
+    pub predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash> AllPaths<N> {
+    /// Every node that lies on at least one cheapest route to a goal.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut seen: HashSet<N> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+        while let Some(node) = stack.pop() {
+            for prev in self.predecessors.get(&node).into_iter().flatten() {
+                if seen.insert(prev.clone()) {
+                    stack.push(prev.clone());
+                }
+            }
+        }
+        seen
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+pub fn bfs<N, FN, IN>(start: N, mut successors: FN, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    parents.insert(start.clone(), None);
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let mut nodes = vec![node];
+            while let Some(Some(prev)) = nodes.last().and_then(|n| parents.get(n)) {
+                nodes.push(prev.clone());
+            }
+            nodes.reverse();
+            return Some(Path {
+                cost: nodes.len() as u64 - 1,
+                nodes,
+            });
+        }
+
+        for next in successors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm; `successors` yields each neighbour with the cost of
+/// the step to it.
+pub fn dijkstra<N, FN, IN>(start: N, successors: FN, is_goal: impl FnMut(&N) -> bool) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let found = Search::run(start, successors, None, is_goal, false)?;
+    Some(Path {
+        cost: found.cost,
+        nodes: found.path(found.goals[0]),
+    })
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost, or the
+/// path found may not be the cheapest.
+pub fn astar<N, FN, IN>(
+    start: N,
+    successors: FN,
+    mut heuristic: impl FnMut(&N) -> u64,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let found = Search::run(start, successors, Some(&mut heuristic), is_goal, false)?;
+    Some(Path {
+        cost: found.cost,
+        nodes: found.path(found.goals[0]),
+    })
+}
+
+/// Like [`dijkstra`], but keeps every predecessor that reaches a node at its
+/// lowest cost and every goal reached at the lowest cost, so all cheapest
+/// routes can be recovered.
+pub fn dijkstra_all<N, FN, IN>(start: N, successors: FN, is_goal: impl FnMut(&N) -> bool) -> Option<AllPaths<N>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, u64)>,
+{
+    let found = Search::run(start, successors, None, is_goal, true)?;
+    let predecessors = found
+        .parents
+        .iter()
+        .enumerate()
+        .filter(|(_, parents)| !parents.is_empty())
+        .map(|(i, parents)| {
+            let parents = parents.iter().map(|&p| found.nodes[p].clone()).collect();
+            (found.nodes[i].clone(), parents)
+        })
+        .collect();
+
+    Some(AllPaths {
+        cost: found.cost,
+        path: found.path(found.goals[0]),
+        goals: found.goals.iter().map(|&g| found.nodes[g].clone()).collect(),
+        predecessors,
+    })
+}
+
+// Nodes are interned so the heap only ever orders plain numbers.
+struct Search<N> {
+    nodes: Vec<N>,
+    parents: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+    cost: u64,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn run<FN, IN>(
+        start: N,
+        mut successors: FN,
+        mut heuristic: Option<&mut dyn FnMut(&N) -> u64>,
+        mut is_goal: impl FnMut(&N) -> bool,
+        all: bool,
+    ) -> Option<Self>
+    where
+        FN: FnMut(&N) -> IN,
+        IN: IntoIterator<Item = (N, u64)>,
+    {
+        let mut estimate = |node: &N| heuristic.as_mut().map_or(0, |h| h(node));
+
+        let mut index = HashMap::new();
+        let mut search = Search {
+            nodes: vec![start.clone()],
+            parents: vec![Vec::new()],
+            goals: Vec::new(),
+            cost: 0,
+        };
+        let mut best = vec![0];
+        index.insert(start.clone(), 0);
+
+        let mut heap = BinaryHeap::new();
+        heap.push((Reverse(estimate(&start)), Reverse(0), 0));
+
+        while let Some((Reverse(priority), Reverse(cost), i)) = heap.pop() {
+            if cost > best[i] {
+                continue;
+            }
+            if !search.goals.is_empty() && priority > search.cost {
+                break;
+            }
+
+            if is_goal(&search.nodes[i]) {
+                search.cost = cost;
+                search.goals.push(i);
+                if !all {
+                    break;
+                }
+                continue;
+            }
+
+            for (next, step) in successors(&search.nodes[i]) {
+                let next_cost = cost + step;
+                let j = *index.entry(next).or_insert_with_key(|next| {
+                    search.nodes.push(next.clone());
+                    search.parents.push(Vec::new());
+                    best.push(u64::MAX);
+                    search.nodes.len() - 1
+                });
+
+                if next_cost < best[j] {
+                    best[j] = next_cost;
+                    search.parents[j] = vec![i];
+                    heap.push((Reverse(next_cost + estimate(&search.nodes[j])), Reverse(next_cost), j));
+                } else if all && next_cost == best[j] && !search.parents[j].contains(&i) {
+                    search.parents[j].push(i);
+                }
+            }
+        }
+
+        (!search.goals.is_empty()).then_some(search)
+    }
+
+    // Follows the first parent of each node back to the start.
+    fn path(&self, goal: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[goal].clone()];
+        let mut i = goal;
+        while let Some(&prev) = self.parents[i].first() {
+            path.push(self.nodes[prev].clone());
+            i = prev;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small weighted graph with two cheapest routes from 0 to 4:
+    // 0 -1-> 1 -1-> 3 -1-> 4 and 0 -2-> 2 -1-> 4, plus a dearer 0 -5-> 4.
+    fn edges(node: &u32) -> Vec<(u32, u64)> {
+        match node {
+            0 => vec![(1, 1), (2, 2), (4, 5)],
+            1 => vec![(3, 1)],
+            2 => vec![(4, 1)],
+            3 => vec![(4, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(0u32, |&n| edges(&n).into_iter().map(|(m, _)| m), |&n| n == 4).unwrap();
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes, vec![0, 4]);
+        assert_eq!(bfs(0u32, |&n| edges(&n).into_iter().map(|(m, _)| m), |&n| n == 9), None);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let path = dijkstra(0u32, edges, |&n| n == 4).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes.first(), Some(&0));
+        assert_eq!(path.nodes.last(), Some(&4));
+
+        let path = astar(0u32, edges, |&n| u64::from(n < 4), |&n| n == 4).unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(dijkstra(0u32, edges, |&n| n == 9), None);
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        let all = dijkstra_all(0u32, edges, |&n| n == 4).unwrap();
+        assert_eq!(all.cost, 3);
+        assert_eq!(all.goals, vec![4]);
+        let mut preds = all.predecessors[&4].clone();
+        preds.sort();
+        assert_eq!(preds, vec![2, 3]);
+        let mut nodes: Vec<u32> = all.nodes().into_iter().collect();
+        nodes.sort();
+        assert_eq!(nodes, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::AocError;
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::UInt(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as _)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int: i32, i64);
+answer_from!(UInt: u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+/// One part of one day's puzzle.
+pub trait Solution: Sync {
+    fn day(&self) -> u32;
+    fn part(&self) -> u32;
+    fn solve(&self, input: &str) -> Result<Answer, AocError>;
+
+    /// Parses `input` without solving it, so `bench` can time parsing on
+    /// its own. Days that parse as they go keep the default no-op.
+    fn parse(&self, input: &str) -> Result<(), AocError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from("co,de,ka,ta").to_string(), "co,de,ka,ta");
+    }
+}
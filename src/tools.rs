@@ -0,0 +1,234 @@
+//! Ways to look inside individual days beyond their answers, used by the
+//! `aoc2024` binary. Each function takes the day's puzzle input.
+
+use crate::bigint::BigUint;
+use crate::d17_1::{self, Computer, Stop};
+use crate::d18_1;
+use crate::d6_1::{self, Trajectory};
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::{d24_1, d24_2};
+use crate::AocError;
+use std::fmt::Write as _;
+use std::io;
+use std::time::Duration;
+
+// The day 6 map and the guard's walk on it, with `obstruction` placed first.
+fn guard_walk(input: &str, obstruction: Option<Point>) -> Result<(Grid<char>, Trajectory), AocError> {
+    let (grid, start_pos, start_dir) = d6_1::parse_map(input)?;
+    if let Some(pos) = obstruction {
+        if !grid.contains(pos) {
+            return Err(AocError::InvalidInput(format!("obstruction {},{} is off the map", pos.x, pos.y)));
+        }
+        if pos == start_pos {
+            return Err(AocError::InvalidInput(format!("obstruction {},{} is where the guard starts", pos.x, pos.y)));
+        }
+    }
+    let trajectory = d6_1::simulate_guard_path(&grid, start_pos, start_dir, obstruction);
+    Ok((grid, trajectory))
+}
+
+fn guard_outcome(trajectory: &Trajectory) -> String {
+    if trajectory.loops {
+        "the guard walks in a loop\n".to_string()
+    } else {
+        format!("the guard leaves the map after visiting {} cells\n", trajectory.visited())
+    }
+}
+
+/// The day 6 map with the guard's whole route drawn on it, and whether the
+/// guard leaves or loops. `obstruction` is an extra obstacle as in part 2.
+pub fn day6_route(input: &str, obstruction: Option<Point>) -> Result<String, AocError> {
+    let (grid, trajectory) = guard_walk(input, obstruction)?;
+    Ok(format!("{}\n{}", d6_1::render(&grid, &trajectory, obstruction, false), guard_outcome(&trajectory)))
+}
+
+/// Animates the guard's route on a terminal, one step every `delay`, with
+/// colors if `color`.
+pub fn day6_replay(
+    input: &str,
+    obstruction: Option<Point>,
+    delay: Duration,
+    color: bool,
+    out: &mut impl io::Write,
+) -> Result<(), AocError> {
+    let (grid, trajectory) = guard_walk(input, obstruction)?;
+    let frames = d6_1::frames(&grid, &trajectory, obstruction, color);
+    d6_1::play(frames, delay, out)
+        .and_then(|()| write!(out, "{}", guard_outcome(&trajectory)))
+        .map_err(|err| AocError::InvalidState(format!("cannot draw the replay: {}", err)))
+}
+
+/// The day 17 program as labelled pseudocode, one statement per line.
+pub fn day17_disassembly(input: &str) -> Result<String, AocError> {
+    let computer: Computer = d17_1::parse_input(input)?;
+    d17_1::pretty_print(&computer.program)
+}
+
+/// Runs the day 17 program one step per line until it halts, reaches one of
+/// `breakpoints` or has run `budget` instructions (by default as many as
+/// the solver allows), then prints why it stopped and what it output.
+/// Registers are unbounded, so any input runs. Also returns whether the
+/// program stopped before using up its budget.
+pub fn day17_trace(input: &str, breakpoints: &[usize], budget: Option<u64>) -> Result<(String, bool), AocError> {
+    let mut computer: Computer<BigUint> = d17_1::parse_input(input)?.with_trace();
+    computer.breakpoints.extend(breakpoints);
+    if budget.is_some() {
+        computer.budget = budget;
+    }
+    let stop = computer.resume()?;
+
+    let mut out = String::new();
+    for step in computer.trace.iter().flatten() {
+        writeln!(out, "{}", step).unwrap();
+    }
+    let steps = computer.trace.as_ref().map_or(0, Vec::len);
+    match stop {
+        Stop::Halted => writeln!(out, "halted after {} steps", steps),
+        Stop::Breakpoint(ip) => writeln!(
+            out,
+            "stopped at breakpoint {}: A = {}, B = {}, C = {}",
+            ip, computer.register_a, computer.register_b, computer.register_c
+        ),
+        Stop::OutOfBudget => writeln!(out, "stopped after the budget of {} steps", steps),
+    }
+    .unwrap();
+    let outputs: Vec<String> = computer.outputs.iter().map(i64::to_string).collect();
+    writeln!(out, "output: {}", outputs.join(",")).unwrap();
+    Ok((out, stop != Stop::OutOfBudget))
+}
+
+/// The day 18 walk to the exit when byte `i` lands at time step `i` in a
+/// `grid_size` square: the arrival time, then the cell the walker stands on
+/// at each time step.
+pub fn day18_race(input: &str, grid_size: usize) -> Result<String, AocError> {
+    let bytes = d18_1::parse_input(input)?;
+    let path = d18_1::race_corruption(&bytes, grid_size)?.ok_or(AocError::NoSolution)?;
+    let mut out = format!("arrives at time {}\n", path.cost);
+    for (time, cell) in path.nodes.iter().enumerate() {
+        writeln!(out, "{:>5}  {},{}", time, cell.x, cell.y).unwrap();
+    }
+    Ok(out)
+}
+
+/// The day 24 circuit as Graphviz DOT, with every gate that breaks the
+/// adder's rules drawn in red.
+pub fn day24_dot(input: &str) -> Result<String, AocError> {
+    Ok(d24_2::highlighted_dot(&d24_1::parse_input(input)?))
+}
+
+/// Checks that the day 24 circuit adds x and y into z once each pair of
+/// output wires in `swaps` is swapped, on edge cases and `cases` random
+/// operands drawn from `seed`. Returns the smallest failing case, if any.
+pub fn day24_check(input: &str, swaps: &[(String, String)], cases: usize, seed: u64) -> Result<Option<String>, AocError> {
+    let swaps: Vec<(&str, &str)> = swaps.iter().map(|(a, b)| (a.as_str(), b.as_str())).collect();
+    let circuit = d24_1::parse_input(input)?.with_swaps(&swaps)?;
+    Ok(d24_2::check_adder(&circuit, cases, seed)?.map(|counterexample| counterexample.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY17: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+    const DAY6: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn test_day6_route() {
+        let route = day6_route(DAY6, None).unwrap();
+        assert!(route.starts_with("....#.....\n....+---+#\n"));
+        assert!(route.ends_with("\nthe guard leaves the map after visiting 41 cells\n"));
+
+        let route = day6_route(DAY6, Some(Point::new(3, 6))).unwrap();
+        assert!(route.contains(".#.O^"));
+        assert!(route.ends_with("\nthe guard walks in a loop\n"));
+
+        assert!(day6_route(DAY6, Some(Point::new(10, 0))).is_err());
+        assert!(day6_route(DAY6, Some(Point::new(4, 6))).is_err());
+    }
+
+    #[test]
+    fn test_day6_replay() {
+        let mut out = Vec::new();
+        day6_replay(DAY6, None, Duration::ZERO, false, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 55);
+        assert!(out.ends_with("......#v..\nthe guard leaves the map after visiting 41 cells\n"));
+    }
+
+    #[test]
+    fn test_day17_disassembly() {
+        assert_eq!(
+            day17_disassembly(DAY17).unwrap(),
+            "loop:
+    A = A >> 1;
+    out A % 8;
+    if A != 0 goto loop;
+"
+        );
+    }
+
+    #[test]
+    fn test_day17_trace() {
+        let (trace, stopped) = day17_trace(DAY17, &[], None).unwrap();
+        assert!(stopped);
+        let lines: Vec<&str> = trace.lines().collect();
+        assert_eq!(lines[0], "   0  adv 1    A: 729 -> 364");
+        assert_eq!(lines[1], "   2  out A    out: 4");
+        assert_eq!(lines[lines.len() - 2], "halted after 30 steps");
+        assert_eq!(lines[lines.len() - 1], "output: 4,6,3,5,6,3,5,2,1,0");
+
+        let (trace, _) = day17_trace(DAY17, &[4], None).unwrap();
+        assert!(trace.ends_with("stopped at breakpoint 4: A = 364, B = 0, C = 0\noutput: 4\n"));
+
+        let (trace, stopped) = day17_trace(DAY17, &[], Some(5)).unwrap();
+        assert!(!stopped);
+        assert_eq!(trace.lines().count(), 7);
+        assert!(trace.ends_with("stopped after the budget of 5 steps\noutput: 4,6\n"));
+    }
+
+    #[test]
+    fn test_day24_check() {
+        // A one-bit adder with its sum and carry swapped
+        let input = "x00: 0\ny00: 0\n\nx00 XOR y00 -> z01\nx00 AND y00 -> z00\n";
+        let swap = [("z00".to_string(), "z01".to_string())];
+        assert_eq!(day24_check(input, &swap, 10, 1), Ok(None));
+        assert_eq!(
+            day24_check(input, &[], 10, 1).unwrap().as_deref(),
+            Some("z00 is wrong for x = 1, y = 0: expected z = 01, got 10")
+        );
+        assert!(day24_check(input, &[("z00".to_string(), "q".to_string())], 10, 1).is_err());
+    }
+
+    #[test]
+    fn test_day18_race() {
+        // (0, 1) closes before the walker gets there, (1, 0) only after
+        let race = day18_race("1,1\n0,1\n1,0\n", 3).unwrap();
+        assert_eq!(
+            race,
+            "arrives at time 4
+    0  0,0
+    1  1,0
+    2  2,0
+    3  2,1
+    4  2,2
+"
+        );
+        assert_eq!(day18_race("1,0\n0,1\n", 3), Err(AocError::NoSolution));
+        assert!(day18_race("1,0\n", 0).is_err());
+    }
+}
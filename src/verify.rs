@@ -0,0 +1,144 @@
+use crate::answers::Answers;
+use crate::input::InputSource;
+use crate::Solution;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Outcome of checking one solution against its recorded answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The solver's answer matches the recorded one.
+    Pass,
+    /// The solver returned an answer that differs from the recorded one.
+    Wrong,
+    /// The solver returned an error or panicked.
+    Fail,
+    /// There is no input or no recorded answer to check against.
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Status::Pass => "pass",
+            Status::Wrong => "wrong",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    /// Why the check could not pass, for anything but `Pass` and `Wrong`.
+    pub detail: Option<String>,
+}
+
+/// Runs `solution` on `input` and compares the result with `answers`.
+pub fn verify(solution: &dyn Solution, input: Option<&str>, answers: &Answers) -> Report {
+    let (day, part) = (solution.day(), solution.part());
+    let expected = answers.get(day, part).map(str::to_string);
+    let mut report = Report {
+        day,
+        part,
+        status: Status::Missing,
+        answer: None,
+        expected,
+        detail: None,
+    };
+
+    let Some(input) = input else {
+        report.detail = Some("no input".to_string());
+        return report;
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.solve(input))) {
+        Ok(Ok(answer)) => {
+            let answer = answer.to_string();
+            report.status = match &report.expected {
+                Some(expected) if *expected == answer => Status::Pass,
+                Some(_) => Status::Wrong,
+                None => {
+                    report.detail = Some("no expected answer".to_string());
+                    Status::Missing
+                }
+            };
+            report.answer = Some(answer);
+        }
+        Ok(Err(err)) => {
+            report.status = Status::Fail;
+            report.detail = Some(err.to_string());
+        }
+        Err(_) => {
+            report.status = Status::Fail;
+            report.detail = Some("solver panicked".to_string());
+        }
+    }
+    report
+}
+
+/// Verifies every solution in turn, loading each day's input once.
+pub fn verify_all<'a>(
+    solutions: impl IntoIterator<Item = &'a dyn Solution>,
+    source: &InputSource,
+    answers: &Answers,
+) -> Vec<Report> {
+    let mut reports = Vec::new();
+    let mut current: Option<(u32, Option<String>)> = None;
+
+    for solution in solutions {
+        let day = solution.day();
+        if current.as_ref().map(|(d, _)| *d) != Some(day) {
+            current = Some((day, source.load(day).ok()));
+        }
+        let input = current.as_ref().and_then(|(_, input)| input.as_deref());
+        reports.push(verify(solution, input, answers));
+    }
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, AocError};
+
+    struct Echo;
+
+    impl Solution for Echo {
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn part(&self) -> u32 {
+            1
+        }
+
+        fn solve(&self, input: &str) -> Result<Answer, AocError> {
+            match input {
+                "" => Err(AocError::NoSolution),
+                "panic" => panic!("boom"),
+                _ => Ok(input.into()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_statuses() {
+        let answers = Answers::parse("[d1]\npart1 = 42").unwrap();
+
+        assert_eq!(verify(&Echo, Some("42"), &answers).status, Status::Pass);
+        assert_eq!(verify(&Echo, Some("41"), &answers).status, Status::Wrong);
+        assert_eq!(verify(&Echo, Some(""), &answers).status, Status::Fail);
+        assert_eq!(verify(&Echo, Some("panic"), &answers).status, Status::Fail);
+        assert_eq!(verify(&Echo, None, &answers).status, Status::Missing);
+        let report = verify(&Echo, Some("42"), &Answers::default());
+        assert_eq!(report.status, Status::Missing);
+        assert_eq!(report.detail.as_deref(), Some("no expected answer"));
+    }
+}